@@ -1,23 +1,36 @@
-#![feature(array_windows)]
-
 use std::{
     cmp::Reverse,
-    collections::{BTreeMap, BTreeSet},
+    collections::{BTreeMap, BTreeSet, HashMap},
     env,
-    fmt::Debug,
+    fmt::{Debug, Display},
     io::{BufWriter, Write},
     iter,
     path::Path,
+    time::{SystemTime, UNIX_EPOCH},
 };
 
 use anyhow::Context as _;
 use fs_err::{self as fs, DirEntry, File};
 use proc_macro2::{Ident, Span, TokenStream};
 use quote::{quote, ToTokens};
-use serde::{Deserialize, Serialize};
+use serde::{ser::SerializeStruct, Deserialize, Serialize, Serializer};
 use serde_json::json;
 use tera::{Context, Tera};
 
+/// Modules of the app that are needed here too. The build script doesn't have
+/// the app's dependencies, so they must not depend on anything but `std`, and
+/// it only uses part of each.
+#[allow(dead_code)]
+#[path = "src"]
+mod shared {
+    pub mod link;
+    pub mod markup;
+    pub mod rustdoc;
+    pub mod version;
+}
+
+use shared::{link::LinkKind, markup, rustdoc, version::RustVersion};
+
 #[derive(Serialize)]
 struct Data {
     versions: Vec<FeatureList>,
@@ -26,15 +39,28 @@ struct Data {
 
 #[derive(Deserialize, Serialize)]
 struct VersionData {
-    /// Rust version number, e.g. "1.0.0"
+    /// Rust version number, e.g. "1.0"
+    #[serde(skip_deserializing, serialize_with = "serialize_display")]
+    number: RustVersion,
+    /// The channel (stable / beta / nightly), computed from the release dates
+    /// and the build date
     #[serde(skip_deserializing)]
-    number: String,
-    /// The channel (stable / beta / nightly)
-    #[serde(default)]
     channel: Channel,
+    /// Channel set by hand in versions.toml. Only used to warn when it
+    /// disagrees with the computed channel.
+    #[serde(rename(deserialize = "channel"), default, skip_serializing)]
+    declared_channel: Option<Channel>,
+    /// Channel that is used instead of the computed one, for special cases
+    /// like a release that was delayed
+    #[serde(default, skip_serializing)]
+    channel_override: Option<Channel>,
     /// Release date, in format "yyyy-mm-dd"
     #[serde(skip_serializing_if = "Option::is_none")]
     release_date: Option<String>,
+    /// Whether the release date is projected from the six-week release cycle
+    /// rather than known
+    #[serde(skip_deserializing, skip_serializing_if = "std::ops::Not::not")]
+    release_date_estimated: bool,
     /// Release notes (https://github.com/rust-lang/rust/blob/master/RELEASES.md#{anchor})
     #[serde(skip_serializing_if = "Option::is_none")]
     release_notes: Option<String>,
@@ -44,6 +70,32 @@ struct VersionData {
     /// GitHub milestone id (https://github.com/rust-lang/rust/milestone/{id})
     #[serde(skip_serializing_if = "Option::is_none")]
     gh_milestone_id: Option<u64>,
+    /// Links built from the fields above
+    #[serde(skip_deserializing, skip_serializing_if = "Vec::is_empty")]
+    links: Vec<LinkData>,
+    /// Patch releases of this version, from the `x.y.z` entries in
+    /// versions.toml
+    #[serde(skip_deserializing, skip_serializing_if = "Vec::is_empty")]
+    patch_releases: Vec<PatchRelease>,
+}
+
+#[derive(Serialize)]
+struct PatchRelease {
+    /// Rust version number, including the patch component, e.g. "1.52.1"
+    #[serde(serialize_with = "serialize_display")]
+    number: RustVersion,
+    /// Release date, in format "yyyy-mm-dd"
+    #[serde(skip_serializing_if = "Option::is_none")]
+    release_date: Option<String>,
+    /// Release notes (https://github.com/rust-lang/rust/blob/master/RELEASES.md#{anchor})
+    #[serde(skip_serializing_if = "Option::is_none")]
+    release_notes: Option<String>,
+    /// Blog post path (https://blog.rust-lang.org/{path})
+    #[serde(skip_serializing_if = "Option::is_none")]
+    blog_post_path: Option<String>,
+    /// Links built from the fields above
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    links: Vec<LinkData>,
 }
 
 #[derive(Serialize)]
@@ -61,6 +113,10 @@ struct FeatureList {
 struct FeatureData {
     /// Short description to identify the feature
     title: String,
+    /// A paragraph about the feature, in the same bit of Markdown as the
+    /// title (see `src/markup.rs`)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    description: Option<String>,
     /// Feature flag name, for things that were previously or are still Rust
     /// nightly features with such a thing (`#![feature(...)]`)
     #[serde(skip_serializing_if = "Option::is_none")]
@@ -91,6 +147,17 @@ struct FeatureData {
     /// Unstable book path (https://doc.rust-lang.org/unstable-book/{path})
     #[serde(skip_serializing_if = "Option::is_none")]
     unstable_book_path: Option<String>,
+    /// Rust Reference path (https://doc.rust-lang.org/reference/{path})
+    #[serde(skip_serializing_if = "Option::is_none")]
+    reference_path: Option<String>,
+    /// URL of the comment that started the final comment period or otherwise
+    /// decided on stabilization (https://github.com/rust-lang/...)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    fcp_comment_url: Option<String>,
+    /// URL of a discussion on Zulip (https://rust-lang.zulipchat.com/...) or
+    /// the internals forum (https://internals.rust-lang.org/...)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    discussion_url: Option<String>,
     /// Language items (functions, structs, modules) that are part of this
     /// feature (unless this feature is exactly one item and that item is
     /// already used as the title)
@@ -99,9 +166,63 @@ struct FeatureData {
     /// Aliases (alternatives to the title of this feature)
     #[serde(default, skip_serializing_if = "Vec::is_empty")]
     aliases: Vec<String>,
+    /// Links built from the fields above
+    #[serde(skip_deserializing, skip_serializing_if = "Vec::is_empty")]
+    links: Vec<LinkData>,
+    /// `items` with their documentation URL, if they are written as paths
+    #[serde(skip)]
+    resolved_items: Vec<ItemData>,
+}
+
+/// A link with its full URL, see `src/link.rs`.
+struct LinkData {
+    kind: LinkKind,
+    url: String,
+}
+
+impl LinkData {
+    /// Builds the links for the given targets, skipping the missing ones.
+    fn collect(
+        targets: impl IntoIterator<Item = (LinkKind, Option<String>)>,
+    ) -> anyhow::Result<Vec<Self>> {
+        let present = targets.into_iter().filter_map(|(kind, target)| Some((kind, target?)));
+        present.map(|(kind, target)| Ok(Self { kind, url: kind.url(&target)? })).collect()
+    }
 }
 
-#[derive(Copy, Clone, Debug, Deserialize, Serialize)]
+impl Serialize for LinkData {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        let mut s = serializer.serialize_struct("Link", 3)?;
+        s.serialize_field("kind", self.kind.key())?;
+        s.serialize_field("label", self.kind.label())?;
+        s.serialize_field("url", &self.url)?;
+        s.end()
+    }
+}
+
+impl ToTokens for LinkData {
+    fn to_tokens(&self, tokens: &mut TokenStream) {
+        let kind = Ident::new(&format!("{:?}", self.kind), Span::call_site());
+        let url = &self.url;
+        tokens.extend(quote! { Link { kind: LinkKind::#kind, url: #url } });
+    }
+}
+
+/// An item of a feature, see `src/rustdoc.rs`.
+struct ItemData {
+    code: String,
+    url: Option<String>,
+}
+
+impl ToTokens for ItemData {
+    fn to_tokens(&self, tokens: &mut TokenStream) {
+        let code = &self.code;
+        let url = option_literal(&self.url);
+        tokens.extend(quote! { Item { code: #code, url: #url } });
+    }
+}
+
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Deserialize, Serialize)]
 #[serde(rename_all = "lowercase")]
 enum Channel {
     Stable,
@@ -109,8 +230,7 @@ enum Channel {
     Nightly,
 }
 
-/// Not specifying the channel in features.toml is equivalent to specifying
-/// "stable"
+/// Placeholder until the channel is computed in `assign_channels`
 impl Default for Channel {
     fn default() -> Self {
         Self::Stable
@@ -120,11 +240,13 @@ impl Default for Channel {
 fn main() -> anyhow::Result<()> {
     println!("cargo:rerun-if-changed=data");
     println!("cargo:rerun-if-changed=templates");
+    println!("cargo:rerun-if-env-changed=CANIUSE_BUILD_DATE");
 
     let data = collect_data()?;
 
-    // TODO: Add a filter that replaces `` by <code></code>
-    let tera = Tera::new("templates/*").context("loading templates")?;
+    let mut tera = Tera::new("templates/*").context("loading templates")?;
+    tera.register_filter("markdown", MarkdownFilter);
+    tera.register_filter("url", UrlFilter);
     fs::create_dir_all("public")?;
 
     let ctx = Context::from_serialize(&data)?;
@@ -134,6 +256,13 @@ fn main() -> anyhow::Result<()> {
     let nightly_html = BufWriter::new(File::create("public/nightly.html")?);
     tera.render_to("nightly.html", &ctx, nightly_html).context("rendering nightly.html")?;
 
+    let not_found_html = BufWriter::new(File::create("public/404.html")?);
+    tera.render_to("404.html", &ctx, not_found_html).context("rendering 404.html")?;
+
+    render_static_pages(&tera, &data)?;
+    write_sitemap(&data).context("writing sitemap.xml")?;
+    write_badges(&data).context("writing badges")?;
+
     let (code, json) = generate_output(data);
 
     let mut features_rs =
@@ -146,15 +275,112 @@ fn main() -> anyhow::Result<()> {
     Ok(())
 }
 
+/// Renders the bit of Markdown in titles and descriptions to HTML.
+struct MarkdownFilter;
+
+impl tera::Filter for MarkdownFilter {
+    fn filter(
+        &self,
+        value: &tera::Value,
+        _args: &HashMap<String, tera::Value>,
+    ) -> tera::Result<tera::Value> {
+        let text = tera::try_get_value!("markdown", "value", String, value);
+        Ok(markup::to_html(&text).into())
+    }
+
+    fn is_safe(&self) -> bool {
+        true
+    }
+}
+
+/// Escapes a URL for an attribute value. Tera's default escaping would also
+/// turn every `/` into `&#x2F;`.
+struct UrlFilter;
+
+impl tera::Filter for UrlFilter {
+    fn filter(
+        &self,
+        value: &tera::Value,
+        _args: &HashMap<String, tera::Value>,
+    ) -> tera::Result<tera::Value> {
+        let url = tera::try_get_value!("url", "value", String, value);
+        let escaped = url
+            .replace('&', "&amp;")
+            .replace('"', "&quot;")
+            .replace('<', "&lt;")
+            .replace('>', "&gt;");
+        Ok(escaped.into())
+    }
+
+    fn is_safe(&self) -> bool {
+        true
+    }
+}
+
 fn collect_data() -> anyhow::Result<Data> {
     let mut data = Data {
         versions: Vec::new(),
         unstable: FeatureList { version: None, features: Vec::new() },
     };
 
-    let mut versions: BTreeMap<String, VersionData> =
+    let entries: BTreeMap<String, VersionData> =
         toml::from_str(&fs::read_to_string("data/versions.toml")?)?;
 
+    let mut versions = BTreeMap::new();
+    let mut patch_releases = Vec::new();
+    for (number, mut version_data) in entries {
+        version_data.number = number.parse().context("reading versions.toml")?;
+        version_data.links = LinkData::collect([
+            (LinkKind::BlogPost, version_data.blog_post_path.clone()),
+            (LinkKind::ReleaseNotes, version_data.release_notes.clone()),
+            (LinkKind::GhMilestone, version_data.gh_milestone_id.map(|id| id.to_string())),
+        ])
+        .with_context(|| format!("reading {number} in versions.toml"))?;
+        match version_data.number.patch {
+            None => {
+                versions.insert(version_data.number, version_data);
+            }
+            Some(_) => patch_releases.push(version_data),
+        }
+    }
+
+    patch_releases.sort_unstable_by_key(|v| v.number);
+    for v in patch_releases {
+        let minor_number = v.number.without_patch();
+        anyhow::ensure!(
+            v.number.patch != Some(0),
+            "`{}` has to be defined as `{minor_number}`",
+            v.number,
+        );
+        anyhow::ensure!(
+            v.declared_channel.is_none() && v.channel_override.is_none(),
+            "patch release {} can't have a channel",
+            v.number,
+        );
+        anyhow::ensure!(
+            v.gh_milestone_id.is_none(),
+            "patch release {} can't have a GitHub milestone",
+            v.number,
+        );
+
+        let minor_version = versions
+            .get_mut(&minor_number)
+            .with_context(|| format!("patch release {} of undefined version", v.number))?;
+        minor_version.patch_releases.push(PatchRelease {
+            number: v.number,
+            release_date: v.release_date,
+            release_notes: v.release_notes,
+            blog_post_path: v.blog_post_path,
+            links: v.links,
+        });
+    }
+
+    // Versions without any features are not part of the output, but they
+    // still have to be taken into account here
+    assign_channels(versions.values_mut(), &build_date()?);
+    project_release_dates(versions.values_mut());
+
+    let mut unresolved_items = Vec::new();
     for entry in fs::read_dir("data")? {
         let dir = entry?;
         if dir.file_name() == "versions.toml" {
@@ -167,37 +393,156 @@ fn collect_data() -> anyhow::Result<Data> {
 
         let dir_name = dir.file_name().into_string().unwrap();
 
-        let features = match dir_name.as_str() {
-            "unstable" => &mut data.unstable.features,
+        let (features, channel) = match dir_name.as_str() {
+            "unstable" => (&mut data.unstable.features, None),
             _ => {
-                let (number, mut version_data) = versions
-                    .remove_entry(&dir_name)
+                let number = dir_name
+                    .parse()
+                    .with_context(|| format!("unexpected directory data/{dir_name}"))?;
+                let version_data = versions
+                    .remove(&number)
                     .unwrap_or_else(|| panic!("version {dir_name} not defined in versions.toml"));
-                version_data.number = number;
 
+                let channel = version_data.channel;
                 data.versions
                     .push(FeatureList { version: Some(version_data), features: Vec::new() });
-                &mut data.versions.last_mut().unwrap().features
+                (&mut data.versions.last_mut().unwrap().features, Some(channel))
             }
         };
 
-        collect_features(dir, &dir_name, features)?;
+        collect_features(dir, &dir_name, channel, features, &mut unresolved_items)?;
     }
 
-    data.versions.sort_unstable_by_key(|v| {
-        let num_str = &v.version.as_ref().unwrap().number;
-        assert!(&num_str[..2] == "1.");
-        let num: u16 = num_str[2..].parse().unwrap();
-        Reverse(num)
-    });
+    anyhow::ensure!(
+        unresolved_items.is_empty(),
+        "{} items could not be linked to their documentation:\n{}\n\
+         Every item except for methods and associated constants of types has \
+         to be listed in `ITEMS` in src/rustdoc.rs.",
+        unresolved_items.len(),
+        unresolved_items.join("\n"),
+    );
+
+    data.versions.sort_unstable_by_key(|v| Reverse(v.version.as_ref().unwrap().number));
 
     Ok(data)
 }
 
+/// Works out the channel of every version for the given build date, from the
+/// six-week release train: Versions released on or before that date are
+/// stable, the one after the latest stable release is in beta and any newer
+/// ones are on nightly.
+fn assign_channels<'a>(versions: impl Iterator<Item = &'a mut VersionData>, build_date: &str) {
+    let mut versions: Vec<_> = versions.collect();
+    versions.sort_unstable_by_key(|v| v.number);
+
+    let latest_stable = versions
+        .iter()
+        .filter(|v| v.release_date.as_deref().is_some_and(|date| date <= build_date))
+        .map(|v| v.number)
+        .max();
+
+    let mut beta = None;
+    for v in versions {
+        let computed = match latest_stable {
+            Some(stable) if v.number <= stable => Channel::Stable,
+            Some(_) if beta.is_none() => {
+                beta = Some(v.number);
+                Channel::Beta
+            }
+            _ => Channel::Nightly,
+        };
+
+        if let Some(declared) = v.declared_channel {
+            if declared != computed && v.channel_override.is_none() {
+                println!(
+                    "cargo:warning=versions.toml declares {} as {declared:?}, \
+                     but it is {computed:?} as of {build_date}",
+                    v.number,
+                );
+            }
+        }
+
+        v.channel = v.channel_override.unwrap_or(computed);
+    }
+}
+
+/// Fills in the release date of versions newer than the latest one with a
+/// known release date, assuming a new version is released every six weeks.
+fn project_release_dates<'a>(versions: impl Iterator<Item = &'a mut VersionData>) {
+    let mut versions: Vec<_> = versions.collect();
+    versions.sort_unstable_by_key(|v| v.number);
+
+    let Some(anchor_idx) = versions.iter().rposition(|v| v.release_date.is_some()) else {
+        return;
+    };
+    let anchor_days = days_from_civil(versions[anchor_idx].release_date.as_deref().unwrap());
+
+    for (releases_after_anchor, v) in (1..).zip(&mut versions[anchor_idx + 1..]) {
+        v.release_date = Some(civil_from_days(anchor_days + releases_after_anchor * 42));
+        v.release_date_estimated = true;
+    }
+}
+
+/// Returns the date channels are computed for, in format "yyyy-mm-dd". That is
+/// `CANIUSE_BUILD_DATE`, which `cargo xtask build` always sets. Plain cargo
+/// builds fall back to the current date (UTC), but as the build script only
+/// reruns when the data changes, their channels can be outdated.
+fn build_date() -> anyhow::Result<String> {
+    if let Ok(date) = env::var("CANIUSE_BUILD_DATE") {
+        let valid = date.len() == 10
+            && date.char_indices().all(|(i, c)| match i {
+                4 | 7 => c == '-',
+                _ => c.is_ascii_digit(),
+            });
+        anyhow::ensure!(valid, "CANIUSE_BUILD_DATE must be in format yyyy-mm-dd, got `{date}`");
+        return Ok(date);
+    }
+
+    let days = SystemTime::now().duration_since(UNIX_EPOCH)?.as_secs() / 86400;
+    Ok(civil_from_days(days as i64))
+}
+
+/// Converts a date in format "yyyy-mm-dd" to days since the unix epoch, see
+/// http://howardhinnant.github.io/date_algorithms.html#days_from_civil
+fn days_from_civil(date: &str) -> i64 {
+    let parse = |s: &str| -> i64 {
+        s.parse().unwrap_or_else(|_| panic!("invalid date `{date}`, expected yyyy-mm-dd"))
+    };
+    let (month, day) = (parse(&date[5..7]), parse(&date[8..10]));
+    let year = parse(&date[..4]) - i64::from(month <= 2);
+
+    let era = year / 400;
+    let yoe = year - era * 400;
+    let doy = (153 * (if month > 2 { month - 3 } else { month + 9 }) + 2) / 5 + day - 1;
+    let doe = yoe * 365 + yoe / 4 - yoe / 100 + doy;
+    era * 146097 + doe - 719468
+}
+
+/// Converts days since the unix epoch to a date in format "yyyy-mm-dd", see
+/// http://howardhinnant.github.io/date_algorithms.html#civil_from_days
+fn civil_from_days(days: i64) -> String {
+    let z = days + 719468;
+    let era = z / 146097;
+    let doe = z % 146097;
+    let yoe = (doe - doe / 1460 + doe / 36524 - doe / 146096) / 365;
+    let doy = doe - (365 * yoe + yoe / 4 - yoe / 100);
+    let mp = (5 * doy + 2) / 153;
+    let day = doy - (153 * mp + 2) / 5 + 1;
+    let month = if mp < 10 { mp + 3 } else { mp - 9 };
+    let year = yoe + era * 400 + i64::from(month <= 2);
+
+    format!("{year:04}-{month:02}-{day:02}")
+}
+
+/// Reads the features in `dir`, which are stabilized in a version on the given
+/// channel, or unstable for `None`. Items that can't be linked are added to
+/// `unresolved_items`, so they can be reported all at once.
 fn collect_features(
     dir: DirEntry,
     dir_name: &str,
+    channel: Option<Channel>,
     features: &mut Vec<FeatureData>,
+    unresolved_items: &mut Vec<String>,
 ) -> anyhow::Result<()> {
     for entry in fs::read_dir(dir.path())? {
         let file = entry?;
@@ -210,8 +555,28 @@ fn collect_features(
             }
         };
 
-        let feature = toml::from_str(&fs::read_to_string(file.path())?)
+        let mut feature: FeatureData = toml::from_str(&fs::read_to_string(file.path())?)
             .with_context(|| format!("deserializing data/{dir_name}/{file_name}"))?;
+        anyhow::ensure!(
+            !markup::contains_link(&markup::parse(&feature.title)),
+            "title in data/{dir_name}/{file_name} contains a link, which is not possible \
+             because titles are shown as links",
+        );
+        feature.links = feature_links(&feature, channel)
+            .with_context(|| format!("reading data/{dir_name}/{file_name}"))?;
+        for item in &feature.items {
+            match rustdoc::doc_path(item) {
+                Ok(path) => {
+                    let url = path
+                        .map(|path| {
+                            LinkKind::Documentation.url(&format!("{}{path}", docs_prefix(channel)))
+                        })
+                        .transpose()?;
+                    feature.resolved_items.push(ItemData { code: item.clone(), url });
+                }
+                Err(e) => unresolved_items.push(format!("  data/{dir_name}/{file_name}: {e}")),
+            }
+        }
 
         features.push(FeatureData { slug, ..feature });
     }
@@ -219,6 +584,286 @@ fn collect_features(
     Ok(())
 }
 
+fn feature_links(f: &FeatureData, channel: Option<Channel>) -> anyhow::Result<Vec<LinkData>> {
+    let id = |id: Option<u64>| id.map(|id| id.to_string());
+    LinkData::collect([
+        (LinkKind::Rfc, id(f.rfc_id)),
+        (LinkKind::ImplPr, id(f.impl_pr_id)),
+        (LinkKind::TrackingIssue, id(f.tracking_issue_id)),
+        (LinkKind::FcpComment, f.fcp_comment_url.clone()),
+        (LinkKind::StabilizationPr, id(f.stabilization_pr_id)),
+        (LinkKind::Discussion, f.discussion_url.clone()),
+        (
+            LinkKind::Documentation,
+            f.doc_path.as_ref().map(|p| format!("{}{p}", docs_prefix(channel))),
+        ),
+        (LinkKind::Reference, f.reference_path.clone()),
+        (LinkKind::EditionGuide, f.edition_guide_path.clone()),
+        (LinkKind::UnstableBook, f.unstable_book_path.clone()),
+    ])
+}
+
+/// Directory of the documentation for the channel that has the feature, so
+/// links point to docs that include it.
+fn docs_prefix(channel: Option<Channel>) -> &'static str {
+    match channel {
+        Some(Channel::Stable) => "",
+        Some(Channel::Beta) => "beta/",
+        Some(Channel::Nightly) | None => "nightly/",
+    }
+}
+
+/// Renders one page per feature and per version into `public/`. They only
+/// differ from `index.html` in their title and meta tags, which makes links to
+/// them show up with a proper preview when shared.
+fn render_static_pages(tera: &Tera, data: &Data) -> anyhow::Result<()> {
+    fs::create_dir_all("public/features")?;
+    fs::create_dir_all("public/versions")?;
+
+    for list in data.versions.iter().chain(iter::once(&data.unstable)) {
+        let version = list.version.as_ref();
+
+        for f in &list.features {
+            let title = markup::to_plain(&f.title);
+            let availability = match version {
+                Some(v) => match (v.channel, &v.release_date) {
+                    (Channel::Stable, Some(date)) => {
+                        format!("Available since Rust {}, released on {date}.", v.number)
+                    }
+                    (Channel::Stable, None) => format!("Available since Rust {}.", v.number),
+                    (Channel::Beta, _) => {
+                        format!("Stabilized in Rust {}, which is currently in beta.", v.number)
+                    }
+                    (Channel::Nightly, _) => format!(
+                        "Stabilized in Rust {}, which is currently available on nightly.",
+                        v.number
+                    ),
+                },
+                None => match &f.flag {
+                    Some(flag) => {
+                        format!("Unstable, available on nightly through #![feature({flag})].")
+                    }
+                    None => "Unstable.".to_owned(),
+                },
+            };
+
+            let path = format!("/features/{}", f.slug);
+            let description = format!("Can I use {title}? {availability}");
+            render_page(tera, &f.title, &description, &path, &f.links, f.description.as_deref())?;
+        }
+
+        if let Some(v) = version {
+            let number = &v.number;
+            let released = match (v.channel, &v.release_date) {
+                (Channel::Stable, Some(date)) => format!("Rust {number} was released on {date}"),
+                (Channel::Stable, None) => format!("Rust {number} was released"),
+                (_, Some(date)) if v.release_date_estimated => {
+                    format!("Rust {number} is expected to be released around {date}")
+                }
+                (_, Some(date)) => format!("Rust {number} will be released on {date}"),
+                (_, None) => format!("Rust {number} will be released"),
+            };
+            let highlights: Vec<_> =
+                list.features.iter().take(3).map(|f| markup::to_plain(&f.title)).collect();
+            let stabilized = match v.channel {
+                Channel::Stable => "stabilized",
+                Channel::Beta | Channel::Nightly => "will stabilize",
+            };
+            let description = match list.features.len() {
+                0 => format!("{released}."),
+                1 => format!("{released} and {stabilized} {}.", highlights[0]),
+                n => format!(
+                    "{released} and {stabilized} {n} features, including {}.",
+                    highlights.join(", ")
+                ),
+            };
+
+            render_page(
+                tera,
+                &format!("Rust {number}"),
+                &description,
+                &format!("/versions/{number}"),
+                &v.links,
+                None,
+            )?;
+        }
+    }
+
+    let description = format!(
+        "All {} Rust releases with their release dates and the features they stabilized.",
+        data.versions.len()
+    );
+    render_page(tera, "Rust releases", &description, "/versions", &[], None)?;
+
+    let description = "Check which Rust features are available across several Rust versions.";
+    render_page(tera, "Compare versions", description, "/compare", &[], None)?;
+
+    let description = "Keep track of the Rust features you are waiting for.";
+    render_page(tera, "Watchlist", description, "/watchlist", &[], None)?;
+
+    Ok(())
+}
+
+/// Writes a sitemap of all pages, with the release date of the respective
+/// version as the last modification date of feature and version pages.
+fn write_sitemap(data: &Data) -> anyhow::Result<()> {
+    fn write_url(out: &mut impl Write, path: &str, lastmod: Option<&str>) -> std::io::Result<()> {
+        writeln!(out, "  <url>")?;
+        writeln!(out, "    <loc>https://caniuse.rs{path}</loc>")?;
+        if let Some(date) = lastmod {
+            writeln!(out, "    <lastmod>{date}</lastmod>")?;
+        }
+        writeln!(out, "  </url>")
+    }
+
+    // Beta and nightly versions don't have a release date in the past
+    fn stable_release_date(v: &VersionData) -> Option<&str> {
+        match v.channel {
+            Channel::Stable => v.release_date.as_deref(),
+            Channel::Beta | Channel::Nightly => None,
+        }
+    }
+
+    let latest_release =
+        data.versions.iter().filter_map(|l| stable_release_date(l.version.as_ref()?)).max();
+
+    let mut out = BufWriter::new(File::create("public/sitemap.xml")?);
+    writeln!(out, r#"<?xml version="1.0" encoding="UTF-8"?>"#)?;
+    writeln!(out, r#"<urlset xmlns="http://www.sitemaps.org/schemas/sitemap/0.9">"#)?;
+
+    for path in ["/", "/recent", "/unstable", "/versions"] {
+        write_url(&mut out, path, latest_release)?;
+    }
+    write_url(&mut out, "/about", None)?;
+
+    for list in data.versions.iter().chain(iter::once(&data.unstable)) {
+        let lastmod = list.version.as_ref().and_then(stable_release_date);
+        if let Some(v) = &list.version {
+            write_url(&mut out, &format!("/versions/{}", v.number), lastmod)?;
+        }
+        for f in &list.features {
+            write_url(&mut out, &format!("/features/{}", f.slug), lastmod)?;
+        }
+    }
+
+    writeln!(out, "</urlset>")?;
+    out.flush()?;
+
+    Ok(())
+}
+
+/// Writes a shields.io-style SVG badge for every feature to
+/// `public/badges/{slug}.svg` and for every version to
+/// `public/badges/versions/{number}.svg`, colored by channel like in the UI.
+fn write_badges(data: &Data) -> anyhow::Result<()> {
+    fs::create_dir_all("public/badges/versions")?;
+
+    for list in data.versions.iter().chain(iter::once(&data.unstable)) {
+        let version = list.version.as_ref();
+        // Same colors as the `stable`, `beta`, `nightly` and `none` classes
+        // in style.css
+        let color = match version.map(|v| v.channel) {
+            Some(Channel::Stable) => "#0B7220",
+            Some(Channel::Beta) => "#4EC135",
+            Some(Channel::Nightly) => "#D7B90E",
+            None => "#E48416",
+        };
+
+        let message = match version {
+            Some(v) => {
+                let number = v.number.to_string();
+                let path = format!("public/badges/versions/{number}.svg");
+                fs::write(path, badge_svg("Rust", &number, color))?;
+                format!("Rust {number}")
+            }
+            None => "unstable".to_owned(),
+        };
+
+        for f in &list.features {
+            let path = format!("public/badges/{}.svg", f.slug);
+            fs::write(path, badge_svg(&f.slug, &message, color))?;
+        }
+    }
+
+    Ok(())
+}
+
+fn badge_svg(label: &str, message: &str, color: &str) -> String {
+    // Rough width of the text in 11px Verdana, which is what shields.io uses
+    fn text_width(s: &str) -> u32 {
+        let width: f64 = s
+            .chars()
+            .map(|c| match c {
+                'i' | 'j' | 'l' | '.' | ',' | ':' | ';' | '!' | '|' | '\'' => 3.5,
+                'f' | 'r' | 't' | 'I' | ' ' | '(' | ')' | '-' => 4.5,
+                'm' | 'w' | 'M' | 'W' => 10.0,
+                c if c.is_ascii_uppercase() => 8.0,
+                _ => 7.0,
+            })
+            .sum();
+        width.ceil() as u32
+    }
+
+    fn escape(s: &str) -> String {
+        s.replace('&', "&amp;").replace('<', "&lt;").replace('>', "&gt;").replace('"', "&quot;")
+    }
+
+    let label_width = text_width(label) + 10;
+    let message_width = text_width(message) + 10;
+    let width = label_width + message_width;
+    let (label, message) = (escape(label), escape(message));
+
+    format!(
+        r##"<svg xmlns="http://www.w3.org/2000/svg" width="{width}" height="20" role="img" aria-label="{label}: {message}">
+  <title>{label}: {message}</title>
+  <linearGradient id="s" x2="0" y2="100%">
+    <stop offset="0" stop-color="#bbb" stop-opacity=".1"/>
+    <stop offset="1" stop-opacity=".1"/>
+  </linearGradient>
+  <clipPath id="r">
+    <rect width="{width}" height="20" rx="3" fill="#fff"/>
+  </clipPath>
+  <g clip-path="url(#r)">
+    <rect width="{label_width}" height="20" fill="#555"/>
+    <rect x="{label_width}" width="{message_width}" height="20" fill="{color}"/>
+    <rect width="{width}" height="20" fill="url(#s)"/>
+  </g>
+  <g fill="#fff" text-anchor="middle" font-family="Verdana,Geneva,DejaVu Sans,sans-serif" font-size="11">
+    <text x="{label_x}" y="14">{label}</text>
+    <text x="{message_x}" y="14">{message}</text>
+  </g>
+</svg>
+"##,
+        label_x = f64::from(label_width) / 2.0,
+        message_x = f64::from(label_width) + f64::from(message_width) / 2.0,
+    )
+}
+
+/// Renders `public{path}.html`. `title` and `text` can contain Markdown, which
+/// is removed from the title for the `<title>` and meta tags.
+fn render_page(
+    tera: &Tera,
+    title: &str,
+    description: &str,
+    path: &str,
+    links: &[LinkData],
+    text: Option<&str>,
+) -> anyhow::Result<()> {
+    let mut ctx = Context::new();
+    ctx.insert("title", &markup::to_plain(title));
+    ctx.insert("heading", title);
+    ctx.insert("text", &text);
+    ctx.insert("description", description);
+    ctx.insert("path", path);
+    ctx.insert("links", links);
+
+    let file_name = format!("public{path}.html");
+    let html = BufWriter::new(File::create(&file_name)?);
+    tera.render_to("page.html", &ctx, html).with_context(|| format!("rendering {file_name}"))?;
+
+    Ok(())
+}
+
 fn generate_output(data: Data) -> (TokenStream, serde_json::Value) {
     let mut json = json!({ "versions": {}, "features": {} });
 
@@ -226,6 +871,11 @@ fn generate_output(data: Data) -> (TokenStream, serde_json::Value) {
     let mut bigram_index = BTreeMap::new();
     let mut trigram_index = BTreeMap::new();
 
+    let mut flag_index = BTreeMap::<_, BTreeSet<u16>>::new();
+    let mut rfc_index = BTreeMap::<_, BTreeSet<u16>>::new();
+    let mut pr_index = BTreeMap::<_, BTreeSet<u16>>::new();
+    let mut issue_index = BTreeMap::<_, BTreeSet<u16>>::new();
+
     let mut versions = Vec::new();
     let mut features = Vec::new();
 
@@ -233,23 +883,39 @@ fn generate_output(data: Data) -> (TokenStream, serde_json::Value) {
 
     for v in data.versions.into_iter().chain(iter::once(data.unstable)) {
         let v_idx = v.version.as_ref().map(|d| {
-            json["versions"][&d.number] = serde_json::to_value(d).unwrap();
+            let mut version_json = serde_json::to_value(d).unwrap();
+            // Version numbers are strings, this allows JSON consumers to sort
+            // them without parsing
+            version_json["sort_key"] = sort_key(d.number).into();
+            json["versions"][d.number.to_string()] = version_json;
 
             let number = &d.number;
             let channel = Ident::new(&format!("{:?}", d.channel), Span::call_site());
             let release_date = option_literal(&d.release_date);
-            let release_notes = option_literal(&d.release_notes);
-            let blog_post_path = option_literal(&d.blog_post_path);
-            let gh_milestone_id = option_literal(&d.gh_milestone_id);
+            let release_date_estimated = d.release_date_estimated;
+            let links = &d.links;
+            let patch_releases = d.patch_releases.iter().map(|p| {
+                let number = &p.number;
+                let release_date = option_literal(&p.release_date);
+                let links = &p.links;
+
+                quote! {
+                    PatchRelease {
+                        number: #number,
+                        release_date: #release_date,
+                        links: &[#(#links),*],
+                    }
+                }
+            });
 
             versions.push(quote! {
                 VersionData {
                     number: #number,
                     channel: Channel::#channel,
                     release_date: #release_date,
-                    release_notes: #release_notes,
-                    blog_post_path: #blog_post_path,
-                    gh_milestone_id: #gh_milestone_id,
+                    release_date_estimated: #release_date_estimated,
+                    links: &[#(#links),*],
+                    patch_releases: &[#(#patch_releases),*],
                 }
             });
 
@@ -266,25 +932,34 @@ fn generate_output(data: Data) -> (TokenStream, serde_json::Value) {
             add_feature_ngrams::<2>(&mut bigram_index, &f, feat_idx);
             add_feature_ngrams::<3>(&mut trigram_index, &f, feat_idx);
 
+            if let Some(flag) = &f.flag {
+                flag_index.entry(flag.clone()).or_default().insert(feat_idx);
+            }
+            if let Some(id) = f.rfc_id {
+                rfc_index.entry(id).or_default().insert(feat_idx);
+            }
+            for id in f.impl_pr_id.iter().chain(&f.stabilization_pr_id) {
+                pr_index.entry(*id).or_default().insert(feat_idx);
+            }
+            if let Some(id) = f.tracking_issue_id {
+                issue_index.entry(id).or_default().insert(feat_idx);
+            }
+
             json["features"][&f.slug] = {
                 let mut feat_json = serde_json::to_value(&f).unwrap();
                 feat_json["version"] =
-                    serde_json::to_value(v.version.as_ref().map(|d| &d.number)).unwrap();
+                    serde_json::to_value(v.version.as_ref().map(|d| d.number.to_string())).unwrap();
                 feat_json.as_object_mut().unwrap().remove("slug");
                 feat_json
             };
 
             let title = &f.title;
+            let description = option_literal(&f.description);
             let flag = option_literal(&f.flag);
             let slug = f.slug;
-            let rfc_id = option_literal(&f.rfc_id);
-            let impl_pr_id = option_literal(&f.impl_pr_id);
-            let tracking_issue_id = option_literal(&f.tracking_issue_id);
-            let stabilization_pr_id = option_literal(&f.stabilization_pr_id);
-            let doc_path = option_literal(&f.doc_path);
-            let edition_guide_path = option_literal(&f.edition_guide_path);
-            let unstable_book_path = option_literal(&f.unstable_book_path);
-            let items = &f.items;
+            let links = &f.links;
+            let items = &f.resolved_items;
+            let aliases = &f.aliases;
 
             let version = match v_idx {
                 Some(idx) => quote!(Some(&VERSIONS[#idx])),
@@ -294,17 +969,13 @@ fn generate_output(data: Data) -> (TokenStream, serde_json::Value) {
             features.push(quote! {
                 FeatureData {
                     title: #title,
+                    description: #description,
                     flag: #flag,
                     slug: #slug,
                     version: #version,
-                    rfc_id: #rfc_id,
-                    impl_pr_id: #impl_pr_id,
-                    tracking_issue_id: #tracking_issue_id,
-                    stabilization_pr_id: #stabilization_pr_id,
-                    doc_path: #doc_path,
-                    edition_guide_path: #edition_guide_path,
-                    unstable_book_path: #unstable_book_path,
+                    links: &[#(#links),*],
                     items: &[#(#items),*],
+                    aliases: &[#(#aliases),*],
                 }
             });
 
@@ -366,17 +1037,73 @@ fn generate_output(data: Data) -> (TokenStream, serde_json::Value) {
             });
     };
 
+    let flag_feature_index = lookup_index("FEATURE_FLAG_INDEX", quote!(&str), flag_index);
+    let rfc_feature_index = lookup_index("FEATURE_RFC_INDEX", quote!(u64), rfc_index);
+    let pr_feature_index = lookup_index("FEATURE_PR_INDEX", quote!(u64), pr_index);
+    let issue_feature_index = lookup_index("FEATURE_ISSUE_INDEX", quote!(u64), issue_index);
+
     let stream = quote! {
         #versions
         #features
         #monogram_feature_index
         #bigram_feature_index
         #trigram_feature_index
+        #flag_feature_index
+        #rfc_feature_index
+        #pr_feature_index
+        #issue_feature_index
     };
 
     (stream, json)
 }
 
+/// Generates a map from exact keys (feature flags, RFC / PR / issue ids) to
+/// the indices of the features that reference them.
+fn lookup_index<K: ToTokens>(
+    name: &str,
+    key_type: TokenStream,
+    index: BTreeMap<K, BTreeSet<u16>>,
+) -> TokenStream {
+    let name = Ident::new(name, Span::call_site());
+    let insert_stmts = index.into_iter().map(|(key, v)| {
+        quote! {
+            index.insert(#key, &[#(#v),*] as &[u16]);
+        }
+    });
+
+    quote! {
+        #[allow(clippy::unreadable_literal)]
+        pub static #name: once_cell::sync::Lazy<std::collections::HashMap<#key_type, &[u16]>> =
+            once_cell::sync::Lazy::new(|| {
+                let mut index = std::collections::HashMap::new();
+                #(#insert_stmts)*
+                index
+            });
+    }
+}
+
+fn serialize_display<T: Display, S: Serializer>(
+    value: &T,
+    serializer: S,
+) -> Result<S::Ok, S::Error> {
+    serializer.collect_str(value)
+}
+
+/// Maps a version to a number that sorts the same way, as long as no
+/// component is 1000 or above.
+fn sort_key(v: RustVersion) -> u64 {
+    let RustVersion { major, minor, patch } = v;
+    u64::from(major) * 1_000_000 + u64::from(minor) * 1_000 + u64::from(patch.unwrap_or(0))
+}
+
+impl ToTokens for RustVersion {
+    fn to_tokens(&self, tokens: &mut TokenStream) {
+        let RustVersion { major, minor, patch } = self;
+        let patch = option_literal(patch);
+        tokens.extend(quote! { RustVersion::new(#major, #minor, #patch) });
+    }
+}
+
 fn option_literal<T: ToTokens>(opt: &Option<T>) -> TokenStream {
     match opt {
         Some(lit) => quote! { Some(#lit) },
@@ -1,6 +1,6 @@
 use yew::{html, Component, Context, Html};
 
-use crate::util::{home_button, Void};
+use crate::util::{home_button, set_title, Void};
 
 pub struct About;
 
@@ -63,4 +63,8 @@ impl Component for About {
             </>
         }
     }
+
+    fn rendered(&mut self, _: &Context<Self>, _first_render: bool) {
+        set_title(Some("About"));
+    }
 }
@@ -1,30 +1,61 @@
-use std::rc::Rc;
+use std::{mem, rc::Rc};
 
-use gloo_events::EventListener;
+use gloo_events::{EventListener, EventListenerOptions};
+use gloo_timers::callback::Timeout;
 use gloo_utils::document;
 use wasm_bindgen::JsCast;
-use web_sys::{HtmlInputElement, KeyboardEvent};
-use yew::{html, Component, Context, Html, NodeRef};
+use wasm_bindgen_futures::JsFuture;
+use web_sys::{ClipboardEvent, DragEvent, Event, HtmlInputElement, KeyboardEvent};
+use yew::{html, platform::spawn_local, Component, Context, ContextProvider, Html, NodeRef};
 use yew_router::BrowserRouter;
 
 use crate::{
     components::{
         index::{Explore, IndexContents},
-        About, FeaturePage, Header, Index, VersionPage,
+        not_found::Missing,
+        About, Compare, FeaturePage, Header, Index, NotFound, ScrollManager, Shortcuts,
+        VersionList, VersionPage, WatchlistPage,
     },
-    AppRoute, FEATURES, VERSIONS,
+    data::find_version,
+    toolchain::{parse_toolchain_file, Toolchain},
+    util::{has_modifier, is_search_box, is_typing, store_toolchain, stored_toolchain},
+    version::RustVersion,
+    watchlist::Watchlist,
+    AppRoute, UserContext, FEATURES,
 };
 
+/// How long notices are shown.
+const NOTICE_TIMEOUT_MS: u32 = 5_000;
+
 pub struct App {
     input_ref: NodeRef,
     search_query: Rc<String>,
+    show_shortcuts: bool,
+    user: UserContext,
+    /// Short-lived message, e.g. about the result of dropping a file
+    notice: Option<(String, Timeout)>,
 
     _key_listener: EventListener,
+    _paste_listener: EventListener,
+    _dragover_listener: EventListener,
+    _drop_listener: EventListener,
 }
 
 pub enum Msg {
     FocusInput,
     Search(Rc<String>),
+    ClearSearch,
+    ToggleShortcuts,
+    HideShortcuts,
+    SetToolchain(Option<RustVersion>),
+    /// A `rust-toolchain.toml` or `Cargo.toml` was dropped or pasted. Pasted
+    /// text has no file name.
+    ToolchainFile {
+        name: Option<String>,
+        contents: String,
+    },
+    HideNotice,
+    ToggleWatch(&'static str),
 }
 
 impl Component for App {
@@ -33,26 +64,165 @@ impl Component for App {
 
     fn create(ctx: &Context<Self>) -> Self {
         let link2 = ctx.link().clone();
-        let _key_listener = EventListener::new(&document(), "keypress", move |event| {
+        let _key_listener = EventListener::new(&document(), "keydown", move |event| {
             let event = event.dyn_ref::<KeyboardEvent>().expect("wrong event type");
-            if event.key().as_str() == "s" {
-                link2.send_message(Msg::FocusInput);
+            if has_modifier(event) {
+                return;
+            }
+
+            let msg = match event.key().as_str() {
+                // Other inputs, like the ones on the compare page, keep
+                // their own meaning of Escape
+                "Escape" if is_search_box(event) || !is_typing(event) => Msg::ClearSearch,
+                _ if is_typing(event) => return,
+                "s" | "/" => Msg::FocusInput,
+                "?" => Msg::ToggleShortcuts,
+                _ => return,
+            };
+
+            // Don't type the shortcut character into the search box
+            event.prevent_default();
+            link2.send_message(msg);
+        });
+
+        // Pasting or dropping a rust-toolchain.toml or Cargo.toml anywhere sets
+        // the user's version of Rust
+        let link = ctx.link().clone();
+        let _paste_listener = EventListener::new(&document(), "paste", move |event| {
+            if is_typing(event) {
+                return;
+            }
+
+            let event = event.dyn_ref::<ClipboardEvent>().expect("wrong event type");
+            let Some(contents) = event.clipboard_data().and_then(|d| d.get_data("text").ok())
+            else {
+                return;
+            };
+            // Only whole files count, pasting a version number or channel name
+            // while reading a page shouldn't change the settings
+            if is_toml_file(&contents) {
+                link.send_message(Msg::ToolchainFile { name: None, contents });
             }
         });
 
-        Self { input_ref: NodeRef::default(), search_query: Rc::new(String::new()), _key_listener }
+        let options = EventListenerOptions::enable_prevent_default();
+        let _dragover_listener =
+            EventListener::new_with_options(&document(), "dragover", options, |event| {
+                let event = event.dyn_ref::<DragEvent>().expect("wrong event type");
+                let has_files =
+                    event.data_transfer().is_some_and(|dt| dt.types().includes(&"Files".into(), 0));
+                if has_files {
+                    // Allows dropping
+                    event.prevent_default();
+                }
+            });
+
+        let link = ctx.link().clone();
+        let _drop_listener =
+            EventListener::new_with_options(&document(), "drop", options, move |event| {
+                let event = event.dyn_ref::<DragEvent>().expect("wrong event type");
+                let Some(file) = event.data_transfer().and_then(|dt| dt.files()?.get(0)) else {
+                    return;
+                };
+                event.prevent_default();
+
+                let link = link.clone();
+                spawn_local(async move {
+                    let contents =
+                        JsFuture::from(file.text()).await.ok().and_then(|t| t.as_string());
+                    let contents = contents.unwrap_or_default();
+                    link.send_message(Msg::ToolchainFile { name: Some(file.name()), contents });
+                });
+            });
+
+        Self {
+            input_ref: NodeRef::default(),
+            search_query: Rc::new(String::new()),
+            show_shortcuts: false,
+            user: UserContext {
+                toolchain: stored_toolchain(),
+                watchlist: Rc::new(Watchlist::load()),
+                onwatchtoggle: ctx.link().callback(Msg::ToggleWatch),
+            },
+            notice: None,
+            _key_listener,
+            _paste_listener,
+            _dragover_listener,
+            _drop_listener,
+        }
     }
 
-    fn update(&mut self, _: &Context<Self>, msg: Msg) -> bool {
+    fn update(&mut self, ctx: &Context<Self>, msg: Msg) -> bool {
         match msg {
             Msg::FocusInput => {
                 self.input_ref.cast::<HtmlInputElement>().unwrap().focus().unwrap();
                 false
             }
             Msg::Search(query) => {
+                if self.search_query == query {
+                    return false;
+                }
+
                 self.search_query = query;
                 true
             }
+            Msg::ClearSearch => {
+                if self.show_shortcuts {
+                    self.show_shortcuts = false;
+                    return true;
+                }
+
+                // Go through the input event, so the header updates the URL
+                let input = self.input_ref.cast::<HtmlInputElement>().unwrap();
+                input.set_value("");
+                input.dispatch_event(&Event::new("input").unwrap()).unwrap();
+                input.focus().unwrap();
+                false
+            }
+            Msg::ToggleShortcuts => {
+                self.show_shortcuts = !self.show_shortcuts;
+                true
+            }
+            Msg::HideShortcuts => mem::replace(&mut self.show_shortcuts, false),
+            Msg::SetToolchain(toolchain) => {
+                store_toolchain(toolchain);
+                self.user.toolchain = toolchain;
+                true
+            }
+            Msg::ToolchainFile { name, contents } => {
+                let toolchain = parse_toolchain_file(&contents);
+                let version = toolchain.as_ref().and_then(Toolchain::resolve);
+                let text = match (version, name) {
+                    (Some(version), Some(name)) => {
+                        format!("Your Rust version is now {version}, from {name}.")
+                    }
+                    (Some(version), None) => {
+                        format!("Your Rust version is now {version}, from the pasted file.")
+                    }
+                    (None, _) if toolchain.is_some() => {
+                        "That Rust version is not known to this site.".to_owned()
+                    }
+                    (None, Some(name)) => format!("No Rust version found in {name}."),
+                    // Pasting random text shouldn't do anything
+                    (None, None) => return false,
+                };
+                if version.is_some() {
+                    self.update(ctx, Msg::SetToolchain(version));
+                }
+
+                let link = ctx.link().clone();
+                let timeout =
+                    Timeout::new(NOTICE_TIMEOUT_MS, move || link.send_message(Msg::HideNotice));
+                self.notice = Some((text, timeout));
+                true
+            }
+            Msg::HideNotice => self.notice.take().is_some(),
+            Msg::ToggleWatch(slug) => {
+                let watchlist = Rc::make_mut(&mut self.user.watchlist);
+                watchlist.toggle(slug);
+                watchlist.store();
+                true
+            }
         }
     }
 
@@ -83,25 +253,49 @@ impl Component for App {
             AppRoute::About => html! { <About /> },
             AppRoute::Feature { name: slug } => match FEATURES.iter().find(|f| f.slug == slug) {
                 Some(&data) => html! { <FeaturePage data={data} /> },
-                None => html! { "error: feature not found!" },
+                None => {
+                    html! { <NotFound missing={Missing::Feature { slug: slug.clone() }} /> }
+                }
             },
-            AppRoute::Version { number } => match VERSIONS.iter().find(|v| v.number == number) {
+            AppRoute::Version { number } => match number.parse().ok().and_then(find_version) {
                 Some(&data) => html! { <VersionPage data={data} /> },
-                None => html! { "error: version not found!" },
+                None => {
+                    html! { <NotFound missing={Missing::Version { number: number.clone() }} /> }
+                }
             },
+            #[allow(clippy::let_unit_value)] // buggy lint
+            AppRoute::Versions => html! { <VersionList /> },
+            #[allow(clippy::let_unit_value)] // buggy lint
+            AppRoute::Compare => html! { <Compare /> },
+            AppRoute::Watchlist => html! { <WatchlistPage /> },
+            AppRoute::NotFound => html! { <NotFound /> },
         };
 
-        let input = self.input_ref.clone();
-        let oninput = ctx.link().callback(move |_e| {
-            Msg::Search(Rc::new(input.cast::<HtmlInputElement>().unwrap().value()))
-        });
+        let onsearch = ctx.link().callback(Msg::Search);
+        let ontoolchainchange = ctx.link().callback(Msg::SetToolchain);
+        let shortcuts = if self.show_shortcuts {
+            html! { <Shortcuts onclose={ctx.link().callback(|_| Msg::HideShortcuts)} /> }
+        } else {
+            html! {}
+        };
+        let notice = match &self.notice {
+            Some((text, _)) => html! { <div class="notice" role="status">{text}</div> },
+            None => html! {},
+        };
 
         html! {
             <BrowserRouter>
-                <Header input_ref={self.input_ref.clone()} oninput={oninput} />
-                <div class="page">
-                    <Switch render={render_route} />
-                </div>
+                <ContextProvider<UserContext> context={self.user.clone()}>
+                    <ScrollManager />
+                    <Header input_ref={self.input_ref.clone()} onsearch={onsearch}
+                        toolchain={self.user.toolchain} ontoolchainchange={ontoolchainchange}
+                        watchlist_changes={self.user.watchlist.num_changes()} />
+                    <div class="page">
+                        <Switch render={render_route} />
+                    </div>
+                    {shortcuts}
+                    {notice}
+                </ContextProvider<UserContext>>
             </BrowserRouter>
         }
     }
@@ -112,3 +306,10 @@ impl Component for App {
         }
     }
 }
+
+/// Whether the text looks like the contents of a `rust-toolchain.toml` or
+/// `Cargo.toml` file rather than a snippet of one.
+fn is_toml_file(text: &str) -> bool {
+    let mut lines = text.lines().map(str::trim).filter(|l| !l.is_empty());
+    lines.clone().count() > 1 && lines.any(|l| l.starts_with('['))
+}
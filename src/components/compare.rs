@@ -0,0 +1,314 @@
+use web_sys::{Event, HtmlInputElement, HtmlSelectElement};
+use yew::{html, Component, Context, Html, TargetCast};
+use yew_router::scope_ext::{LocationHandle, RouterScopeExt};
+
+use crate::{
+    export::{download, Table},
+    markup, scroll,
+    util::{home_button, query_param, set_title, view_text},
+    version::RustVersion,
+    AppRoute, FeatureData, RouterLink, VersionData, FEATURES, VERSIONS,
+};
+
+/// A matrix of which features are available in which versions. The selection
+/// is kept in the `versions` and `features` query parameters, so it can be
+/// shared by URL.
+pub struct Compare {
+    /// Selected versions, from oldest to newest
+    versions: Vec<&'static VersionData>,
+    /// Selected features, in the order they were added
+    features: Vec<&'static FeatureData>,
+
+    _location_listener: Option<LocationHandle>,
+}
+
+pub enum Msg {
+    LocationChanged,
+    AddVersion(Event),
+    RemoveVersion(RustVersion),
+    AddFeature(Event),
+    RemoveFeature(&'static str),
+    ExportCsv,
+    ExportMarkdown,
+}
+
+impl Component for Compare {
+    type Message = Msg;
+    type Properties = ();
+
+    fn create(ctx: &Context<Self>) -> Self {
+        let _location_listener =
+            ctx.link().add_location_listener(ctx.link().callback(|_| Msg::LocationChanged));
+
+        let mut this = Self { versions: Vec::new(), features: Vec::new(), _location_listener };
+        this.read_query(ctx);
+        this
+    }
+
+    fn update(&mut self, ctx: &Context<Self>, msg: Msg) -> bool {
+        match msg {
+            Msg::LocationChanged => {
+                self.read_query(ctx);
+                true
+            }
+            Msg::AddVersion(ev) => {
+                let select: HtmlSelectElement = ev.target_unchecked_into();
+                let number = select.value().parse().ok();
+                select.set_value("");
+
+                if let Some(v) = VERSIONS.iter().find(|v| Some(v.number) == number) {
+                    let mut versions = self.versions.clone();
+                    versions.push(v);
+                    self.write_query(ctx, &versions, &self.features);
+                }
+                false
+            }
+            Msg::RemoveVersion(number) => {
+                let mut versions = self.versions.clone();
+                versions.retain(|v| v.number != number);
+                self.write_query(ctx, &versions, &self.features);
+                false
+            }
+            Msg::AddFeature(ev) => {
+                let input: HtmlInputElement = ev.target_unchecked_into();
+                let value = input.value();
+                let value = value.trim();
+
+                // The input suggests slugs, but typing the exact title works too
+                let feature = FEATURES.iter().find(|f| f.slug == value).or_else(|| {
+                    FEATURES.iter().find(|f| markup::to_plain(f.title).eq_ignore_ascii_case(value))
+                });
+                if let Some(f) = feature {
+                    input.set_value("");
+
+                    let mut features = self.features.clone();
+                    features.push(f);
+                    self.write_query(ctx, &self.versions, &features);
+                }
+                false
+            }
+            Msg::RemoveFeature(slug) => {
+                let mut features = self.features.clone();
+                features.retain(|f| f.slug != slug);
+                self.write_query(ctx, &self.versions, &features);
+                false
+            }
+            Msg::ExportCsv => {
+                download("caniuse-comparison.csv", "text/csv", &self.table(false).to_csv());
+                false
+            }
+            Msg::ExportMarkdown => {
+                download("caniuse-comparison.md", "text/markdown", &self.table(true).to_markdown());
+                false
+            }
+        }
+    }
+
+    fn changed(&mut self, _: &Context<Self>, _old_props: &Self::Properties) -> bool {
+        false
+    }
+
+    fn view(&self, ctx: &Context<Self>) -> Html {
+        let version_options = VERSIONS
+            .iter()
+            .filter(|v| !self.versions.iter().any(|sv| sv.number == v.number))
+            .map(|v| {
+                let number = v.number.to_string();
+                html! { <option value={number.clone()}>{"Rust "}{number}</option> }
+            });
+        let feature_options = FEATURES
+            .iter()
+            .filter(|f| !self.features.iter().any(|sf| sf.slug == f.slug))
+            .map(|f| html! { <option value={f.slug} label={markup::to_plain(f.title)} /> });
+
+        let pickers = html! {
+            <div class="compare-pickers">
+                <select aria-label="Add a version"
+                    onchange={ctx.link().callback(Msg::AddVersion)}>
+                    <option value="" selected=true>{"Add version…"}</option>
+                    { for version_options }
+                </select>
+                <input type="text" list="compare-features" placeholder="Add feature…"
+                    aria-label="Add a feature" onchange={ctx.link().callback(Msg::AddFeature)} />
+                <datalist id="compare-features">{ for feature_options }</datalist>
+            </div>
+        };
+
+        let matrix = if self.versions.is_empty() || self.features.is_empty() {
+            html! {
+                <p>
+                    {"Pick the versions and features to compare. The selection is part of the \
+                      URL, so you can bookmark or share it."}
+                </p>
+            }
+        } else {
+            self.view_matrix(ctx)
+        };
+
+        html! {
+            <>
+                {home_button()}
+                <div class="box compare">
+                    <h3 class="title">{"Compare versions"}</h3>
+                    {pickers}
+                    {matrix}
+                </div>
+            </>
+        }
+    }
+
+    fn rendered(&mut self, _: &Context<Self>, _first_render: bool) {
+        set_title(Some("Compare versions"));
+    }
+}
+
+impl Compare {
+    fn read_query(&mut self, ctx: &Context<Self>) {
+        let location = ctx.link().location();
+        let param = |name| location.as_ref().and_then(|l| query_param(l, name)).unwrap_or_default();
+
+        let mut versions: Vec<&'static VersionData> = param("versions")
+            .split(',')
+            .filter_map(|s| {
+                let number: RustVersion = s.parse().ok()?;
+                VERSIONS.iter().find(|v| v.number == number)
+            })
+            .collect();
+        versions.sort_unstable_by_key(|v| v.number);
+        versions.dedup_by_key(|v| v.number);
+
+        let mut features: Vec<&'static FeatureData> = Vec::new();
+        for slug in param("features").split(',') {
+            if let Some(f) = FEATURES.iter().find(|f| f.slug == slug) {
+                if !features.iter().any(|sf| sf.slug == slug) {
+                    features.push(f);
+                }
+            }
+        }
+
+        self.versions = versions;
+        self.features = features;
+    }
+
+    fn write_query(
+        &self,
+        ctx: &Context<Self>,
+        versions: &[&VersionData],
+        features: &[&FeatureData],
+    ) {
+        let mut versions: Vec<_> = versions.iter().map(|v| v.number).collect();
+        versions.sort_unstable();
+        let versions: Vec<_> = versions.iter().map(ToString::to_string).collect();
+        let features: Vec<_> = features.iter().map(|f| f.slug).collect();
+
+        // The location listener picks up the new selection
+        let query = [("versions", versions.join(",")), ("features", features.join(","))];
+        scroll::replace_entry();
+        ctx.link().navigator().unwrap().replace_with_query(&AppRoute::Compare, &query).unwrap();
+    }
+
+    fn view_matrix(&self, ctx: &Context<Self>) -> Html {
+        let version_headers = self.versions.iter().map(|v| {
+            let number = v.number;
+            html! {
+                <th scope="col">
+                    <RouterLink to={AppRoute::Version { number: number.to_string() }}>
+                        {"Rust "}{number.to_string()}
+                    </RouterLink>
+                    <button type="button" class="remove" title="Remove version"
+                        onclick={ctx.link().callback(move |_| Msg::RemoveVersion(number))}>
+                        {"×"}
+                    </button>
+                </th>
+            }
+        });
+
+        let rows = self.features.iter().map(|f| {
+            let slug = f.slug;
+            let cells = self.versions.iter().map(|v| {
+                if f.is_available_in(v.number) {
+                    html! { <td class="yes" title="Available">{"✓"}</td> }
+                } else {
+                    html! { <td class="no" title="Not available">{"✗"}</td> }
+                }
+            });
+
+            html! {
+                <tr>
+                    <th scope="row">
+                        <RouterLink to={AppRoute::Feature { name: slug.into() }}>
+                            {view_text(f.title)}
+                        </RouterLink>
+                        <button type="button" class="remove" title="Remove feature"
+                            onclick={ctx.link().callback(move |_| Msg::RemoveFeature(slug))}>
+                            {"×"}
+                        </button>
+                    </th>
+                    <td>{since(f)}</td>
+                    { for cells }
+                </tr>
+            }
+        });
+
+        html! {
+            <>
+                <div class="matrix-wrapper">
+                    <table class="matrix">
+                        <thead>
+                            <tr>
+                                <th scope="col">{"Feature"}</th>
+                                <th scope="col">{"Since"}</th>
+                                { for version_headers }
+                            </tr>
+                        </thead>
+                        <tbody>{ for rows }</tbody>
+                    </table>
+                </div>
+                <div class="export">
+                    <button type="button" class="button"
+                        onclick={ctx.link().callback(|_| Msg::ExportCsv)}>
+                        {"Export CSV"}
+                    </button>
+                    <button type="button" class="button"
+                        onclick={ctx.link().callback(|_| Msg::ExportMarkdown)}>
+                        {"Export Markdown"}
+                    </button>
+                </div>
+            </>
+        }
+    }
+
+    /// The matrix as a table for exporting. Markdown keeps the backticks in
+    /// feature titles.
+    fn table(&self, markdown: bool) -> Table {
+        let header = ["Feature".to_owned(), "Since".to_owned()]
+            .into_iter()
+            .chain(self.versions.iter().map(|v| format!("Rust {}", v.number)))
+            .collect();
+
+        let rows = self
+            .features
+            .iter()
+            .map(|f| {
+                let title = if markdown { f.title.to_owned() } else { markup::to_plain(f.title) };
+                let cells = self.versions.iter().map(|v| {
+                    if f.is_available_in(v.number) {
+                        "✓".to_owned()
+                    } else {
+                        "✗".to_owned()
+                    }
+                });
+                [title, since(f)].into_iter().chain(cells).collect()
+            })
+            .collect();
+
+        Table { header, rows }
+    }
+}
+
+fn since(f: &FeatureData) -> String {
+    match f.version {
+        Some(v) => v.number.to_string(),
+        None => "unstable".to_owned(),
+    }
+}
@@ -0,0 +1,62 @@
+use std::rc::Rc;
+
+use yew::{html, AttrValue, Component, Context, Html, Properties};
+
+use crate::{
+    export::{download, feature_table, Format},
+    FeatureData,
+};
+
+#[derive(Clone, PartialEq, Properties)]
+pub struct Props {
+    pub features: Rc<[FeatureData]>,
+    /// File name of the export, without the extension
+    pub file_name: AttrValue,
+}
+
+/// Buttons for downloading a list of features as CSV, Markdown or JSON.
+pub struct ExportButtons;
+
+impl Component for ExportButtons {
+    type Message = Format;
+    type Properties = Props;
+
+    fn create(_: &Context<Self>) -> Self {
+        Self
+    }
+
+    fn update(&mut self, ctx: &Context<Self>, format: Format) -> bool {
+        let props = ctx.props();
+        let table = feature_table(&props.features, format == Format::Markdown);
+        let file_name = format!("{}.{}", props.file_name, format.extension());
+        download(&file_name, format.mime_type(), &format.write(&table));
+        false
+    }
+
+    fn changed(&mut self, _: &Context<Self>, _old_props: &Self::Properties) -> bool {
+        true
+    }
+
+    fn view(&self, ctx: &Context<Self>) -> Html {
+        if ctx.props().features.is_empty() {
+            return html! {};
+        }
+
+        let button = |format, label| {
+            html! {
+                <button type="button" class="button"
+                    onclick={ctx.link().callback(move |_| format)}>
+                    {label}
+                </button>
+            }
+        };
+
+        html! {
+            <div class="export">
+                {button(Format::Csv, "Export CSV")}
+                {button(Format::Markdown, "Export Markdown")}
+                {button(Format::Json, "Export JSON")}
+            </div>
+        }
+    }
+}
@@ -1,9 +1,14 @@
-use yew::{html, Classes, Component, Context, Html, Properties};
+use std::mem;
+
+use wasm_bindgen::JsCast;
+use web_sys::HtmlElement;
+use yew::{context::ContextHandle, html, Classes, Component, Context, Html, NodeRef, Properties};
+use yew_router::Routable;
 
 use crate::{
     data::{Channel, FeatureData},
-    util::{view_text, Void},
-    AppRoute, RouterLink,
+    util::{view_release_date, view_text, watch_button},
+    AppRoute, RouterLink, UserContext,
 };
 
 #[derive(Clone, PartialEq, Properties)]
@@ -11,29 +16,68 @@ pub struct Props {
     pub data: FeatureData,
     #[prop_or(true)]
     pub show_version: bool,
+    /// Whether this entry is selected through keyboard navigation
+    #[prop_or_default]
+    pub selected: bool,
+    /// Whether links go to caniuse.rs rather than through the router, for
+    /// embedding the entry in other sites
+    #[prop_or_default]
+    pub external: bool,
+}
+
+pub struct FeatureEntry {
+    node_ref: NodeRef,
+    /// Whether the entry just became selected and should receive focus
+    focus_pending: bool,
+    user: UserContext,
+
+    _user_handle: Option<ContextHandle<UserContext>>,
 }
 
-pub struct FeatureEntry;
+pub enum Msg {
+    UserChanged(UserContext),
+}
 
 impl Component for FeatureEntry {
-    type Message = Void;
+    type Message = Msg;
     type Properties = Props;
 
-    fn create(_: &Context<Self>) -> Self {
-        Self
+    fn create(ctx: &Context<Self>) -> Self {
+        let (user, _user_handle) = UserContext::subscribe(ctx, Msg::UserChanged);
+        Self {
+            node_ref: NodeRef::default(),
+            focus_pending: ctx.props().selected,
+            user,
+            _user_handle,
+        }
     }
 
-    fn update(&mut self, _: &Context<Self>, void: Self::Message) -> bool {
-        match void {}
+    fn update(&mut self, _: &Context<Self>, msg: Msg) -> bool {
+        match msg {
+            Msg::UserChanged(user) => {
+                self.user = user;
+                true
+            }
+        }
     }
 
-    fn changed(&mut self, _: &Context<Self>, _old_props: &Self::Properties) -> bool {
+    fn changed(&mut self, ctx: &Context<Self>, old_props: &Self::Properties) -> bool {
+        self.focus_pending = ctx.props().selected && !old_props.selected;
         true
     }
 
     fn view(&self, ctx: &Context<Self>) -> Html {
         let f = &ctx.props().data;
         let v = f.version;
+        let external = ctx.props().external;
+        let link = |to: AppRoute, classes: Classes, contents: Html| {
+            if external {
+                let href = format!("https://caniuse.rs{}", to.to_path());
+                html! { <a href={href} class={classes}>{contents}</a> }
+            } else {
+                html! { <RouterLink to={to} classes={classes}>{contents}</RouterLink> }
+            }
+        };
 
         let maybe_flag = match f.flag {
             Some(flag) if v.is_none() => html! {
@@ -46,6 +90,9 @@ impl Component for FeatureEntry {
             }
         };
 
+        // Features that can't be used with the user's version are greyed out
+        let unavailable_in = self.user.toolchain.filter(|&t| !f.is_available_in(t));
+
         let support_indicator = if ctx.props().show_version {
             match v {
                 None => html! { <div class="version none">{"Unstable"}</div> },
@@ -58,9 +105,17 @@ impl Component for FeatureEntry {
 
                     html! {
                         <div class={classes}>
-                            <RouterLink to={AppRoute::Version { number: version.number.into() }}>
-                                {"Rust "}{version.number}
-                            </RouterLink>
+                            {link(
+                                AppRoute::Version { number: version.number.to_string() },
+                                Classes::new(),
+                                html! { <>{"Rust "}{version.number.to_string()}</> },
+                            )}
+                            if version.channel != Channel::Stable && version.release_date.is_some() {
+                                <div class="release-date">{view_release_date(version)}</div>
+                            }
+                            if let Some(toolchain) = unavailable_in {
+                                <div class="release-date">{"not in "}{toolchain.to_string()}</div>
+                            }
                         </div>
                     }
                 }
@@ -69,17 +124,54 @@ impl Component for FeatureEntry {
             html! {}
         };
 
+        let watchlist = &self.user.watchlist;
+        let maybe_change = match watchlist.previous_status(f.slug) {
+            Some(previous) => html! {
+                <div class="change">{"Changed since your last visit, was "}{previous}</div>
+            },
+            None => html! {},
+        };
+
         let classes: Classes = "title".into();
+        let mut entry_classes = Classes::from("feature-entry");
+        if ctx.props().selected {
+            entry_classes.push("selected");
+        }
+        if unavailable_in.is_some() {
+            entry_classes.push("unavailable");
+        }
+        if watchlist.previous_status(f.slug).is_some() {
+            entry_classes.push("changed");
+        }
+
         html! {
-            <div class="feature-entry">
+            <div ref={self.node_ref.clone()} class={entry_classes} role="listitem"
+                aria-current={ctx.props().selected.then_some("true")}>
                 <div class="box">
-                    <RouterLink to={AppRoute::Feature { name: f.slug.into() }} classes={classes}>
-                        <h3>{view_text(f.title)}</h3>
-                    </RouterLink>
+                    {link(
+                        AppRoute::Feature { name: f.slug.into() },
+                        classes,
+                        html! { <h3>{view_text(f.title)}</h3> },
+                    )}
                     {maybe_flag}
+                    {maybe_change}
+                    if !external {
+                        {watch_button(f.slug, watchlist.contains(f.slug), &self.user.onwatchtoggle)}
+                    }
                 </div>
                 {support_indicator}
             </div>
         }
     }
+
+    fn rendered(&mut self, _: &Context<Self>, _first_render: bool) {
+        if mem::take(&mut self.focus_pending) {
+            // Move focus to the title link, so pressing enter opens the
+            // feature and the browser scrolls the entry into view
+            let entry = self.node_ref.cast::<HtmlElement>().unwrap();
+            if let Ok(Some(link)) = entry.query_selector("a.title") {
+                link.unchecked_into::<HtmlElement>().focus().unwrap();
+            }
+        }
+    }
 }
@@ -0,0 +1,300 @@
+use std::{cell::RefCell, collections::HashMap, rc::Rc};
+
+use gloo_utils::{document, window};
+use js_sys::Array;
+use wasm_bindgen::{closure::Closure, JsCast, JsValue};
+use web_sys::{Element, IntersectionObserver, IntersectionObserverInit};
+use yew::{html, AttrValue, Component, Context, Html, NodeRef, Properties};
+
+use crate::{components::FeatureEntry, scroll, FeatureData};
+
+/// A list of features that only renders the entries in and around the
+/// viewport. The space taken up by all other entries is filled by spacers
+/// whose height is estimated from the entries rendered so far.
+pub struct FeatureList {
+    list_ref: NodeRef,
+    top_spacer_ref: NodeRef,
+    bottom_spacer_ref: NodeRef,
+    /// Range of rendered entries
+    start: usize,
+    end: usize,
+    /// Average vertical space taken up by one entry, in pixels
+    entry_height: f64,
+    /// Entry to scroll to the top of the viewport after the next render
+    scroll_to: Option<usize>,
+
+    observer: Option<IntersectionObserver>,
+    _observer_callback: Closure<dyn FnMut()>,
+}
+
+pub enum Msg {
+    Recompute,
+}
+
+#[derive(Clone, PartialEq, Properties)]
+pub struct Props {
+    pub features: Rc<[FeatureData]>,
+    /// Index of the entry selected through keyboard navigation. The list
+    /// jumps to it if it isn't rendered yet.
+    #[prop_or_default]
+    pub selected: Option<usize>,
+    /// Identifies the list for rendering the same entries as before when
+    /// navigating back or forward to it, so its scroll position can be
+    /// restored
+    #[prop_or_default]
+    pub position_key: Option<AttrValue>,
+}
+
+/// Number of entries rendered before the first layout measurement
+const INITIAL_ENTRIES: usize = 20;
+/// Rough height of an entry, used before any have been measured
+const INITIAL_ENTRY_HEIGHT: f64 = 70.0;
+
+/// The rendered part of a list that is no longer shown
+#[derive(Clone, Copy)]
+struct SavedRange {
+    start: usize,
+    end: usize,
+    entry_height: f64,
+}
+
+thread_local! {
+    static SAVED_RANGES: RefCell<HashMap<AttrValue, SavedRange>> = RefCell::default();
+}
+
+impl Component for FeatureList {
+    type Message = Msg;
+    type Properties = Props;
+
+    fn create(ctx: &Context<Self>) -> Self {
+        let _observer_callback = Closure::new({
+            let link = ctx.link().clone();
+            move || link.send_message(Msg::Recompute)
+        });
+
+        let mut this = Self {
+            list_ref: NodeRef::default(),
+            top_spacer_ref: NodeRef::default(),
+            bottom_spacer_ref: NodeRef::default(),
+            start: 0,
+            end: 0,
+            entry_height: INITIAL_ENTRY_HEIGHT,
+            scroll_to: None,
+            observer: None,
+            _observer_callback,
+        };
+
+        let props = ctx.props();
+        let saved_range = match &props.position_key {
+            Some(key) if scroll::is_restoring() => {
+                SAVED_RANGES.with(|r| r.borrow().get(key).copied())
+            }
+            _ => None,
+        };
+
+        match saved_range {
+            Some(range) if range.end <= props.features.len() => {
+                this.start = range.start;
+                this.end = range.end;
+                this.entry_height = range.entry_height;
+            }
+            _ => this.reset(ctx),
+        }
+        if let Some(idx) = props.selected {
+            this.ensure_rendered(ctx, idx);
+        }
+
+        this
+    }
+
+    fn update(&mut self, ctx: &Context<Self>, msg: Msg) -> bool {
+        match msg {
+            Msg::Recompute => self.recompute(ctx),
+        }
+    }
+
+    fn changed(&mut self, ctx: &Context<Self>, old_props: &Self::Properties) -> bool {
+        // The list is rebuilt whenever the toolchain changes, even if the
+        // same features end up in it
+        let props = ctx.props();
+        let same_features =
+            props.features.iter().map(|f| f.slug).eq(old_props.features.iter().map(|f| f.slug));
+        if !same_features {
+            self.reset(ctx);
+        }
+        if let Some(idx) = props.selected {
+            self.ensure_rendered(ctx, idx);
+        }
+
+        true
+    }
+
+    fn view(&self, ctx: &Context<Self>) -> Html {
+        let features = &ctx.props().features;
+        let selected = ctx.props().selected;
+
+        let top_height = self.start as f64 * self.entry_height;
+        let bottom_height = (features.len() - self.end) as f64 * self.entry_height;
+
+        let entries = features[self.start..self.end].iter().zip(self.start..).map(|(&f, idx)| {
+            html! {
+                <FeatureEntry key={f.slug} data={f} selected={selected == Some(idx)} />
+            }
+        });
+
+        html! {
+            <div ref={self.list_ref.clone()} class="feature-list" role="list">
+                <div ref={self.top_spacer_ref.clone()} class="spacer" aria-hidden="true"
+                    style={format!("height: {top_height}px")} />
+                <>{ for entries }</>
+                <div ref={self.bottom_spacer_ref.clone()} class="spacer" aria-hidden="true"
+                    style={format!("height: {bottom_height}px")} />
+            </div>
+        }
+    }
+
+    fn rendered(&mut self, ctx: &Context<Self>, first_render: bool) {
+        if first_render {
+            // Whenever one of the spacers comes close to the viewport, other
+            // entries have to be rendered
+            let options = IntersectionObserverInit::new();
+            options.set_root_margin("50% 0px");
+            let thresholds: Array = (0..=10).map(|i| JsValue::from(f64::from(i) / 10.0)).collect();
+            options.set_threshold(&thresholds);
+
+            let observer = IntersectionObserver::new_with_options(
+                self._observer_callback.as_ref().unchecked_ref(),
+                &options,
+            )
+            .unwrap();
+            observer.observe(&self.top_spacer_ref.cast::<Element>().unwrap());
+            observer.observe(&self.bottom_spacer_ref.cast::<Element>().unwrap());
+            self.observer = Some(observer);
+        }
+
+        if let Some(idx) = self.scroll_to.take() {
+            if let Some(entry) = self.entry_element(idx) {
+                let offset = entry.get_bounding_client_rect().top() - covered_height();
+                window().scroll_by_with_x_and_y(0.0, offset);
+            }
+        }
+
+        // The layout may have changed in a way that needs other entries
+        ctx.link().send_message(Msg::Recompute);
+    }
+
+    fn destroy(&mut self, ctx: &Context<Self>) {
+        if let Some(observer) = &self.observer {
+            observer.disconnect();
+        }
+
+        if let Some(key) = &ctx.props().position_key {
+            let range =
+                SavedRange { start: self.start, end: self.end, entry_height: self.entry_height };
+            SAVED_RANGES.with(|r| r.borrow_mut().insert(key.clone(), range));
+        }
+    }
+}
+
+impl FeatureList {
+    fn reset(&mut self, ctx: &Context<Self>) {
+        self.start = 0;
+        self.end = ctx.props().features.len().min(INITIAL_ENTRIES);
+        self.scroll_to = None;
+    }
+
+    /// Renders the entries around `idx` and scrolls to it.
+    fn jump_to(&mut self, ctx: &Context<Self>, idx: usize) {
+        let len = ctx.props().features.len();
+        if idx >= len {
+            return;
+        }
+        self.start = idx.saturating_sub(INITIAL_ENTRIES / 2);
+        self.end = (idx + INITIAL_ENTRIES / 2).min(len);
+        self.scroll_to = Some(idx);
+    }
+
+    /// Jumps to the entry at `idx` unless it is already rendered.
+    fn ensure_rendered(&mut self, ctx: &Context<Self>, idx: usize) {
+        if !(self.start..self.end).contains(&idx) {
+            self.jump_to(ctx, idx);
+        }
+    }
+
+    /// Measures the current layout and updates the range of rendered entries
+    /// if it doesn't cover the viewport plus half a viewport in both
+    /// directions anymore.
+    fn recompute(&mut self, ctx: &Context<Self>) -> bool {
+        // Until the scroll position is restored, the layout is not final
+        if scroll::is_restoring() || self.scroll_to.is_some() {
+            return false;
+        }
+
+        let (top_spacer, bottom_spacer) =
+            match (self.top_spacer_ref.cast::<Element>(), self.bottom_spacer_ref.cast::<Element>())
+            {
+                (Some(top), Some(bottom)) => (top, bottom),
+                _ => return false,
+            };
+
+        let len = ctx.props().features.len();
+        let list_top = top_spacer.get_bounding_client_rect().top();
+        let rows_top = top_spacer.get_bounding_client_rect().bottom() - list_top;
+        let rows_bottom = bottom_spacer.get_bounding_client_rect().top() - list_top;
+
+        let rendered = self.end - self.start;
+        let entry_height = if rendered > 0 {
+            (rows_bottom - rows_top) / rendered as f64
+        } else {
+            self.entry_height
+        };
+
+        // Maps a vertical position relative to the top of the list to the
+        // index of the entry at that position
+        let (start, end, spacer_entry_height) = (self.start, self.end, self.entry_height);
+        let index_at = |pos: f64| {
+            let idx = if pos < rows_top {
+                pos / spacer_entry_height
+            } else if pos < rows_bottom {
+                start as f64 + (pos - rows_top) / entry_height
+            } else {
+                end as f64 + (pos - rows_bottom) / spacer_entry_height
+            };
+            (idx.max(0.0) as usize).min(len)
+        };
+
+        let viewport_height = window().inner_height().unwrap().as_f64().unwrap();
+        let viewport_top = -list_top;
+        let viewport_bottom = viewport_top + viewport_height;
+
+        let needed_start = index_at(viewport_top - viewport_height / 2.0);
+        let needed_end = (index_at(viewport_bottom + viewport_height / 2.0) + 1).min(len);
+        if self.start <= needed_start && needed_end <= self.end {
+            return false;
+        }
+
+        // Render a bit more than needed, so this doesn't have to run again
+        // right away when scrolling on
+        self.start = index_at(viewport_top - viewport_height);
+        self.end = (index_at(viewport_bottom + viewport_height) + 1).min(len);
+        if rendered > 0 {
+            self.entry_height = entry_height;
+        }
+
+        true
+    }
+    fn entry_element(&self, idx: usize) -> Option<Element> {
+        let list = self.list_ref.cast::<Element>()?;
+        // The first child is the top spacer
+        list.children().item((idx - self.start + 1) as u32)
+    }
+}
+
+/// Height of the fixed page header, plus the explore navigation if present.
+fn covered_height() -> f64 {
+    ["header", "nav.explore"]
+        .into_iter()
+        .filter_map(|selector| document().query_selector(selector).ok().flatten())
+        .map(|elem| elem.get_bounding_client_rect().bottom())
+        .fold(0.0, f64::max)
+}
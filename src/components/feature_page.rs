@@ -1,9 +1,9 @@
-use yew::{html, Component, Context, Html, Properties};
+use yew::{context::ContextHandle, html, Component, Context, Html, Properties};
 
 use crate::{
-    data::Channel,
-    util::{home_button, link, maybe_link, view_text, Void},
-    AppRoute, FeatureData, RouterLink,
+    data::Item,
+    util::{home_button, set_title, view_link, view_text, watch_button},
+    AppRoute, FeatureData, RouterLink, UserContext,
 };
 
 #[derive(Clone, PartialEq, Properties)]
@@ -11,18 +11,32 @@ pub struct Props {
     pub data: FeatureData,
 }
 
-pub struct FeaturePage;
+pub struct FeaturePage {
+    user: UserContext,
+
+    _user_handle: Option<ContextHandle<UserContext>>,
+}
+
+pub enum Msg {
+    UserChanged(UserContext),
+}
 
 impl Component for FeaturePage {
-    type Message = Void;
+    type Message = Msg;
     type Properties = Props;
 
-    fn create(_: &Context<Self>) -> Self {
-        Self
+    fn create(ctx: &Context<Self>) -> Self {
+        let (user, _user_handle) = UserContext::subscribe(ctx, Msg::UserChanged);
+        Self { user, _user_handle }
     }
 
-    fn update(&mut self, _: &Context<Self>, void: Self::Message) -> bool {
-        match void {}
+    fn update(&mut self, _: &Context<Self>, msg: Msg) -> bool {
+        match msg {
+            Msg::UserChanged(user) => {
+                self.user = user;
+                true
+            }
+        }
     }
 
     fn changed(&mut self, _: &Context<Self>, _old_props: &Self::Properties) -> bool {
@@ -35,13 +49,41 @@ impl Component for FeaturePage {
         // TODO: Colorization?
         let version = match f.version {
             Some(v) => html! {
-                <RouterLink to={AppRoute::Version { number: v.number.into() }}>
-                    {v.number}
+                <RouterLink to={AppRoute::Version { number: v.number.to_string() }}>
+                    {v.number.to_string()}
                 </RouterLink>
             },
             None => html! { "none (unstable)" },
         };
 
+        let toolchain_info = match self.user.toolchain {
+            Some(toolchain) => {
+                let (class, text) = if f.is_available_in(toolchain) {
+                    ("stable", "available")
+                } else {
+                    ("none", "not available")
+                };
+                html! {
+                    <>
+                        <span>{"In Rust "}{toolchain.to_string()}{":"}</span>
+                        <span class={class}>{text}</span>
+                    </>
+                }
+            }
+            None => html! {},
+        };
+
+        let watchlist = &self.user.watchlist;
+        let change_info = match watchlist.previous_status(f.slug) {
+            Some(previous) => html! {
+                <>
+                    <span>{"On your last visit:"}</span>
+                    <span class="change">{previous}</span>
+                </>
+            },
+            None => html! {},
+        };
+
         let flag_info = match f.flag {
             Some(flag) => html! {
                 <>
@@ -52,44 +94,14 @@ impl Component for FeaturePage {
             None => html! {},
         };
 
-        let maybe_rfc_link =
-            maybe_link("RFC", "https://github.com/rust-lang/rfcs/issues/", f.rfc_id);
-        let maybe_impl_pr_link = maybe_link(
-            "Implementation PR",
-            "https://github.com/rust-lang/rust/pull/",
-            f.impl_pr_id,
-        );
-        let maybe_tracking_issue_link = maybe_link(
-            "Tracking issue",
-            "https://github.com/rust-lang/rust/issues/",
-            f.tracking_issue_id,
-        );
-        let maybe_stabilization_pr_link = maybe_link(
-            "Stabilization PR",
-            "https://github.com/rust-lang/rust/pull/",
-            f.stabilization_pr_id,
-        );
-        let maybe_doc_link = match f.doc_path {
-            Some(path) => {
-                let prefix = match f.version.map_or(Channel::Nightly, |v| v.channel) {
-                    Channel::Nightly => "nightly/",
-                    Channel::Beta => "beta/",
-                    Channel::Stable => "",
-                };
-                link("Documentation", "https://doc.rust-lang.org/", format_args!("{prefix}{path}"))
-            }
-            None => html! {},
+        type QueryLink = yew_router::components::Link<AppRoute, [(&'static str, &'static str); 1]>;
+        let compare_link = html! {
+            <li>
+                <QueryLink to={AppRoute::Compare} query={Some([("features", f.slug)])}>
+                    {"Compare versions"}
+                </QueryLink>
+            </li>
         };
-        let maybe_edition_guide_link = maybe_link(
-            "Edition Guide",
-            "https://doc.rust-lang.org/edition-guide/",
-            f.edition_guide_path,
-        );
-        let maybe_unstable_book_link = maybe_link(
-            "Unstable book",
-            "https://doc.rust-lang.org/unstable-book/",
-            f.unstable_book_path,
-        );
 
         let maybe_items = if f.items.is_empty() {
             html! {}
@@ -104,33 +116,40 @@ impl Component for FeaturePage {
                     <h3 class="title">
                         {view_text(f.title)}
                     </h3>
+                    {watch_button(f.slug, watchlist.contains(f.slug), &self.user.onwatchtoggle)}
                     <div class="info">
                         <span>{"Since version:"}</span>
                         <span>{version}</span>
+                        {toolchain_info}
+                        {change_info}
                         {flag_info}
                     </div>
+                    {for f.description.map(|d| html! { <p class="description">{view_text(d)}</p> })}
                     <ul class="links">
-                        {maybe_rfc_link}
-                        {maybe_impl_pr_link}
-                        {maybe_tracking_issue_link}
-                        {maybe_stabilization_pr_link}
-                        {maybe_doc_link}
-                        {maybe_edition_guide_link}
-                        {maybe_unstable_book_link}
+                        { for f.links.iter().map(view_link) }
+                        {compare_link}
                     </ul>
                     {maybe_items}
                 </div>
             </>
         }
     }
+
+    fn rendered(&mut self, ctx: &Context<Self>, _first_render: bool) {
+        set_title(Some(ctx.props().data.title));
+    }
 }
 
-fn view_items(items: &[&str]) -> Html {
+fn view_items(items: &[Item]) -> Html {
     let items = items.iter().map(|item| {
-        if item.contains('\n') {
-            html! { <li><pre>{item}</pre></li> }
+        let code = if item.code.contains('\n') {
+            html! { <pre>{item.code}</pre> }
         } else {
-            html! { <li><code>{item}</code></li> }
+            html! { <code>{item.code}</code> }
+        };
+        match item.url {
+            Some(url) => html! { <li><a href={url}>{code}</a></li> },
+            None => html! { <li>{code}</li> },
         }
     });
     html! {
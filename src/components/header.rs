@@ -1,41 +1,80 @@
-use std::mem;
+use std::{mem, rc::Rc};
 
 use gloo_events::EventListener;
+use gloo_timers::callback::Timeout;
 use gloo_utils::{body, document_element, window};
 use wasm_bindgen::JsCast;
-use web_sys::{HtmlElement, InputEvent, MouseEvent};
-use yew::{html, Callback, Component, Context, Html, NodeRef, Properties};
-use yew_router::scope_ext::RouterScopeExt;
+use web_sys::{
+    Event, HtmlElement, HtmlInputElement, HtmlSelectElement, InputEvent, KeyboardEvent, MouseEvent,
+};
+use yew::{html, Callback, Component, Context, Html, NodeRef, Properties, TargetCast};
+use yew_router::scope_ext::{LocationHandle, RouterScopeExt};
 
 use crate::{
-    icons::{fa_bars, fa_moon, fa_question_circle, fa_sun},
-    AppRoute, RouterLink,
+    data::find_version,
+    icons::{fa_bars, fa_list_ul, fa_moon, fa_question_circle, fa_star, fa_sun, fa_table},
+    scroll,
+    search::exact_matches,
+    util::{has_modifier, query_param},
+    version::RustVersion,
+    AppRoute, RouterLink, VERSIONS,
 };
 
 pub struct Header {
     is_menu_open: bool,
     document_click_listener: Option<EventListener>,
+    search_timeout: Option<Timeout>,
+
+    _location_listener: Option<LocationHandle>,
 }
 
 pub enum Msg {
     OpenMenu,
     CloseMenu,
     UpdateTheme(&'static str),
+    UpdateToolchain(Option<RustVersion>),
+    /// The query changed, `complete` if it was pasted rather than typed
+    Input {
+        complete: bool,
+    },
+    /// Run the search, `complete` if the query was pasted or submitted with
+    /// Enter rather than being typed
+    CommitSearch {
+        complete: bool,
+    },
+    LocationChanged,
 }
 
 #[derive(Clone, PartialEq, Properties)]
 pub struct Props {
     #[prop_or_default]
     pub input_ref: NodeRef,
-    pub oninput: Callback<InputEvent>,
+    pub onsearch: Callback<Rc<String>>,
+    /// The user's version of Rust, if set
+    pub toolchain: Option<RustVersion>,
+    pub ontoolchainchange: Callback<Option<RustVersion>>,
+    /// Number of watched features that changed since the last visit
+    #[prop_or_default]
+    pub watchlist_changes: usize,
 }
 
+/// How long to wait after the last keystroke before running the search.
+const SEARCH_DEBOUNCE_MS: u32 = 150;
+
 impl Component for Header {
     type Message = Msg;
     type Properties = Props;
 
-    fn create(_: &Context<Self>) -> Self {
-        Self { is_menu_open: false, document_click_listener: None }
+    fn create(ctx: &Context<Self>) -> Self {
+        let _location_listener =
+            ctx.link().add_location_listener(ctx.link().callback(|_| Msg::LocationChanged));
+
+        Self {
+            is_menu_open: false,
+            document_click_listener: None,
+            search_timeout: None,
+            _location_listener,
+        }
     }
 
     fn update(&mut self, ctx: &Context<Self>, msg: Msg) -> bool {
@@ -71,6 +110,108 @@ impl Component for Header {
 
                 true
             }
+            Msg::UpdateToolchain(toolchain) => {
+                if toolchain != ctx.props().toolchain {
+                    ctx.props().ontoolchainchange.emit(toolchain);
+                }
+
+                false
+            }
+            Msg::Input { complete: true } => {
+                // Pasted text is complete, no need to wait for more input
+                self.update(ctx, Msg::CommitSearch { complete: true })
+            }
+            Msg::Input { complete: false } => {
+                // Replacing the previous timeout cancels it
+                self.search_timeout = Some(Timeout::new(SEARCH_DEBOUNCE_MS, {
+                    let link = ctx.link().clone();
+                    move || link.send_message(Msg::CommitSearch { complete: false })
+                }));
+
+                false
+            }
+            Msg::CommitSearch { complete } => {
+                self.search_timeout = None;
+
+                let query = input(ctx).value();
+                let link = ctx.link();
+                let navigator = link.navigator().unwrap();
+
+                // Pasting or submitting a feature flag or an RFC / PR / issue
+                // number that belongs to a single feature leads straight to
+                // that feature. This is not done while typing, where a prefix
+                // of the number might belong to some unrelated feature.
+                if complete {
+                    if let Some([feature]) = exact_matches(&query).as_deref() {
+                        navigator.push(&AppRoute::Feature { name: feature.slug.into() });
+                        ctx.props().onsearch.emit(Rc::new(query));
+                        return false;
+                    }
+                }
+
+                let on_index = link.route::<AppRoute>() == Some(AppRoute::Index);
+                let url_query = link.location().and_then(|l| query_param(&l, "q"));
+
+                if query.is_empty() {
+                    if on_index && url_query.is_some() {
+                        scroll::replace_entry();
+                        navigator.replace(&AppRoute::Index);
+                    }
+                } else if url_query.as_deref() != Some(&query) {
+                    // Only the first keystroke of a new search creates a
+                    // history entry, refinements of it replace that entry
+                    let res = if on_index && url_query.is_some() {
+                        scroll::replace_entry();
+                        navigator.replace_with_query(&AppRoute::Index, &[("q", &query)])
+                    } else {
+                        navigator.push_with_query(&AppRoute::Index, &[("q", &query)])
+                    };
+                    res.unwrap();
+                }
+
+                ctx.props().onsearch.emit(Rc::new(query));
+                false
+            }
+            Msg::LocationChanged => {
+                // Any page can be linked to with `?toolchain=1.xx` to set the
+                // user's version, an empty value unsets it. Anything that is
+                // not a known version leaves the setting alone.
+                let toolchain = ctx.link().location().and_then(|l| query_param(&l, "toolchain"));
+                match toolchain.as_deref() {
+                    Some("") => {
+                        self.update(ctx, Msg::UpdateToolchain(None));
+                    }
+                    Some(value) => {
+                        let version = value.parse().ok().filter(|&v| find_version(v).is_some());
+                        if version.is_some() {
+                            self.update(ctx, Msg::UpdateToolchain(version));
+                        }
+                    }
+                    None => {}
+                }
+
+                // The pages that list features reflect the search query in
+                // their URL, going back to one without `q` clears the search.
+                // On other pages the input keeps its contents.
+                let lists_features = matches!(
+                    ctx.link().route::<AppRoute>(),
+                    Some(AppRoute::Index | AppRoute::RecentlyStabilized | AppRoute::Unstable)
+                );
+                if !lists_features {
+                    return false;
+                }
+
+                let query =
+                    ctx.link().location().and_then(|l| query_param(&l, "q")).unwrap_or_default();
+                let input = input(ctx);
+                if input.value() != query {
+                    self.search_timeout = None;
+                    input.set_value(&query);
+                    ctx.props().onsearch.emit(Rc::new(query));
+                }
+
+                false
+            }
         }
     }
 
@@ -79,6 +220,7 @@ impl Component for Header {
     }
 
     fn view(&self, ctx: &Context<Self>) -> Html {
+        let watchlist_changes = ctx.props().watchlist_changes;
         let (menu_button, menu_classes) = if self.is_menu_open {
             (
                 html! {
@@ -117,28 +259,66 @@ impl Component for Header {
             }
         };
 
-        let link = ctx.link().to_owned();
-        let cb = ctx.props().oninput.clone();
-        let oninput = move |ev| {
-            if link.route::<AppRoute>() != Some(AppRoute::Index) {
-                link.navigator().unwrap().push(&AppRoute::Index);
-            }
-            cb.emit(ev);
+        let toolchain_options = VERSIONS.iter().map(|v| {
+            let number = v.number.to_string();
+            let selected = ctx.props().toolchain.map(RustVersion::without_patch) == Some(v.number);
+            html! { <option value={number.clone()} selected={selected}>{number}</option> }
+        });
+        let toolchain_select = html! {
+            <label class="toolchain" onclick={|ev: MouseEvent| ev.stop_propagation()}>
+                {"My Rust version"}
+                <select onchange={ctx.link().callback(|ev: Event| {
+                    let select: HtmlSelectElement = ev.target_unchecked_into();
+                    Msg::UpdateToolchain(select.value().parse().ok())
+                })}>
+                    <option value="" selected={ctx.props().toolchain.is_none()}>{"Not set"}</option>
+                    { for toolchain_options }
+                </select>
+            </label>
         };
 
+        let oninput = ctx.link().callback(|ev: InputEvent| Msg::Input {
+            complete: ev.input_type() == "insertFromPaste",
+        });
+        let onkeydown = ctx.link().batch_callback(|ev: KeyboardEvent| {
+            (ev.key() == "Enter" && !has_modifier(&ev))
+                .then_some(Msg::CommitSearch { complete: true })
+        });
+
         html! {
             <header>
                 <div class="inner">
                     <div class="caniuse">
                         <label for="query">{"Can I use"}</label>
                         <input ref={ctx.props().input_ref.clone()} id="query" type="search"
-                            oninput={oninput} />
+                            oninput={oninput} onkeydown={onkeydown} />
                         {"?"}
                     </div>
                     <nav aria-label="Site navigation">
                         {menu_button}
                         <ul class={"menu ".to_owned() + menu_classes}>
                             <li>{theme_anchor}</li>
+                            <li>{toolchain_select}</li>
+                            <li>
+                                <RouterLink to={AppRoute::Versions}>
+                                    {fa_list_ul()}{"Releases"}
+                                </RouterLink>
+                            </li>
+                            <li>
+                                <RouterLink to={AppRoute::Compare}>
+                                    {fa_table()}{"Compare"}
+                                </RouterLink>
+                            </li>
+                            <li>
+                                <RouterLink to={AppRoute::Watchlist}>
+                                    {fa_star()}{"Watchlist"}
+                                    if watchlist_changes > 0 {
+                                        <span class="count" title="Changed since your last visit">
+                                            {watchlist_changes}
+                                        </span>
+                                    }
+                                </RouterLink>
+                            </li>
                             <li>
                                 <RouterLink to={AppRoute::About}>
                                     {fa_question_circle()}{"About"}
@@ -150,4 +330,15 @@ impl Component for Header {
             </header>
         }
     }
+
+    fn rendered(&mut self, ctx: &Context<Self>, first_render: bool) {
+        if first_render {
+            // Restore the search query from the URL
+            ctx.link().send_message(Msg::LocationChanged);
+        }
+    }
+}
+
+fn input(ctx: &Context<Header>) -> HtmlInputElement {
+    ctx.props().input_ref.cast::<HtmlInputElement>().unwrap()
 }
@@ -1,37 +1,53 @@
 use std::rc::Rc;
 
 use gloo_events::EventListener;
-use gloo_timers::callback::Timeout;
-use gloo_utils::{body, window};
-use yew::{html, html::Scope, Classes, Component, Context, Html, Properties};
+use gloo_utils::document;
+use wasm_bindgen::JsCast;
+use web_sys::{Element, Event, HtmlElement, KeyboardEvent};
+use yew::{context::ContextHandle, html, AttrValue, Classes, Component, Context, Html, Properties};
 
 use crate::{
-    components::FeatureEntry,
-    search::{extract_search_terms, run_search, InvalidSearchQuery},
-    AppRoute, Channel, FeatureData, RouterLink, FEATURES,
+    components::{ExportButtons, FeatureEntry, FeatureList},
+    data::latest_stable,
+    markup,
+    search::{
+        exact_matches, exact_suggestions, extract_search_terms, run_search, std_path, suggestions,
+        InvalidSearchQuery, SearchResults,
+    },
+    util::{has_modifier, is_search_box, is_typing, search_link, set_title, view_text},
+    version::RustVersion,
+    AppRoute, FeatureData, RouterLink, UserContext, FEATURES, VERSIONS,
 };
 
 pub struct Index {
     show: ContentsToRender,
     current_search_terms: Vec<String>,
-    current_search_results: Vec<FeatureData>,
-    items_visible: usize,
+    current_search_results: SearchResults,
+    /// The explore list or the regular search matches
+    features: Rc<[FeatureData]>,
     search_scores: Vec<(u16, f64)>,
-
-    _scroll_listener: EventListener,
-    _resize_listener: EventListener,
-    _timeout: Timeout,
+    /// Index of the entry selected through keyboard navigation
+    selected: Option<usize>,
+    /// Whether to only list features that are newer than the user's version
+    only_newer: bool,
+    user: UserContext,
+
+    _key_listener: EventListener,
+    _user_handle: Option<ContextHandle<UserContext>>,
 }
 
 enum ContentsToRender {
     Explore(Explore),
     SearchResults,
-    EmptySearchResults,
+    EmptySearchResults { suggestions: Vec<&'static str>, std_path: Option<String> },
     InvalidSearchResults,
 }
 
 pub enum Msg {
-    Update,
+    SelectNext,
+    SelectPrevious,
+    ToggleOnlyNewer,
+    UserChanged(UserContext),
 }
 
 #[derive(Clone, PartialEq, Properties)]
@@ -52,123 +68,117 @@ pub enum Explore {
     Unstable,
 }
 
-const BATCH_SIZE: usize = 20;
-
 impl Component for Index {
     type Message = Msg;
     type Properties = Props;
 
     fn create(ctx: &Context<Self>) -> Self {
-        let _scroll_listener = EventListener::new(&window(), "scroll", {
-            let link = ctx.link().clone();
-            move |_| link.send_message(Msg::Update)
-        });
-        let _resize_listener = EventListener::new(&window(), "resize", {
+        let _key_listener = EventListener::new(&document(), "keydown", {
             let link = ctx.link().clone();
-            move |_| link.send_message(Msg::Update)
-        });
-        let _timeout = create_timeout(ctx.link().clone());
+            move |event| {
+                let event = event.dyn_ref::<KeyboardEvent>().expect("wrong event type");
+                if has_modifier(event) {
+                    return;
+                }
 
-        let mut current_search_terms = Vec::new();
-        let mut current_search_results = Vec::new();
-        let mut search_scores = vec![(0, 0.0); FEATURES.len()];
-        let show = show(
-            ctx.props(),
-            &mut current_search_terms,
-            &mut current_search_results,
-            &mut search_scores,
-        );
+                let msg = match event.key().as_str() {
+                    "ArrowDown" if is_navigating(event) => Msg::SelectNext,
+                    "ArrowUp" if is_navigating(event) => Msg::SelectPrevious,
+                    "j" if !is_typing(event) => Msg::SelectNext,
+                    "k" if !is_typing(event) => Msg::SelectPrevious,
+                    _ => return,
+                };
 
-        Self {
-            show,
-            current_search_terms,
-            current_search_results,
-            items_visible: BATCH_SIZE,
-            search_scores,
+                event.prevent_default();
+                link.send_message(msg);
+            }
+        });
 
-            _scroll_listener,
-            _resize_listener,
-            _timeout,
-        }
+        let (user, _user_handle) = UserContext::subscribe(ctx, Msg::UserChanged);
+        let mut this = Self {
+            show: ContentsToRender::InvalidSearchResults,
+            current_search_terms: Vec::new(),
+            current_search_results: SearchResults::default(),
+            features: Rc::new([]),
+            search_scores: vec![(0, 0.0); FEATURES.len()],
+            selected: None,
+            only_newer: false,
+            user,
+
+            _key_listener,
+            _user_handle,
+        };
+        this.refresh(ctx);
+        this
     }
 
     fn update(&mut self, ctx: &Context<Self>, msg: Msg) -> bool {
         match msg {
-            Msg::Update => {
-                let inner_height = window().inner_height().unwrap().as_f64().unwrap();
-                let scroll_y = window().scroll_y().unwrap();
-                let distance_to_bottom = body().scroll_height() as f64 - scroll_y - inner_height;
-
-                if distance_to_bottom < inner_height {
-                    self.items_visible += BATCH_SIZE;
-                    self._timeout = create_timeout(ctx.link().clone());
+            Msg::SelectNext => {
+                let num_entries = self.num_entries();
+                if num_entries == 0 {
+                    return false;
+                }
 
+                self.selected = Some(match self.selected {
+                    Some(idx) => (idx + 1).min(num_entries - 1),
+                    None => 0,
+                });
+                true
+            }
+            Msg::SelectPrevious => match self.selected {
+                Some(0) => {
+                    // Moving up from the first entry goes back to the search box
+                    self.selected = None;
+                    if let Some(input) = document().get_element_by_id("query") {
+                        input.unchecked_into::<HtmlElement>().focus().unwrap();
+                    }
                     true
-                } else {
-                    false
                 }
+                Some(idx) => {
+                    self.selected = Some(idx - 1);
+                    true
+                }
+                None => false,
+            },
+            Msg::ToggleOnlyNewer => {
+                self.only_newer = !self.only_newer;
+                self.refresh(ctx);
+                true
+            }
+            Msg::UserChanged(user) => {
+                // Watching a feature shouldn't reset the keyboard selection
+                let toolchain_changed = user.toolchain != self.user.toolchain;
+                self.user = user;
+                if toolchain_changed {
+                    self.refresh(ctx);
+                }
+                toolchain_changed
             }
         }
     }
 
     fn changed(&mut self, ctx: &Context<Self>, _old_props: &Self::Properties) -> bool {
-        self.show = show(
-            ctx.props(),
-            &mut self.current_search_terms,
-            &mut self.current_search_results,
-            &mut self.search_scores,
-        );
-
-        self.items_visible = BATCH_SIZE;
-        self._timeout = create_timeout(ctx.link().clone());
-
+        self.refresh(ctx);
         true
     }
 
-    fn view(&self, _: &Context<Self>) -> Html {
+    fn view(&self, ctx: &Context<Self>) -> Html {
         match &self.show {
             ContentsToRender::Explore(ex) => {
-                // Stack slots, to be able to dynamically dispatch to one of
-                // the iterators without an extra allocation
-                let (mut s1, mut s2, mut s3);
-
-                // Features are sorted by version, with unstable ones being at
-                // the very end, which allows using skip_while + take_while
-                // instead of filter to simplify iteration
-                let features: &mut dyn Iterator<Item = &FeatureData> = match ex {
-                    Explore::Stable => {
-                        s1 = FEATURES
-                            .iter()
-                            .skip_while(|f| !f.is_on_channel(Channel::Stable))
-                            .take_while(|f| f.is_on_channel(Channel::Stable));
-
-                        &mut s1
-                    }
-                    Explore::RecentlyStabilized => {
-                        s2 = FEATURES
-                            .iter()
-                            .skip_while(|f| f.is_on_channel(Channel::Nightly))
-                            .take_while(|f| f.is_on_channel(Channel::Beta))
-                            .chain(
-                                FEATURES.iter().take_while(|f| f.is_on_channel(Channel::Nightly)),
-                            );
-
-                        &mut s2
-                    }
-                    Explore::Unstable => {
-                        s3 = FEATURES.iter().skip_while(|f| f.version.is_some());
-
-                        &mut s3
-                    }
-                };
-
                 let index_link_class = active_if(*ex == Explore::Stable);
                 let recent_link_class = active_if(*ex == Explore::RecentlyStabilized);
                 let unstable_link_class = active_if(*ex == Explore::Unstable);
 
-                let list = features
-                    .take(self.items_visible)
-                    .map(|&f| html! { <FeatureEntry key={f.slug} data={f} /> });
+                let position_key = match ex {
+                    Explore::Stable => "stable",
+                    Explore::RecentlyStabilized => "recent",
+                    Explore::Unstable => "unstable",
+                };
+                let position_key = match self.newer_than() {
+                    Some(_) => AttrValue::from(format!("{position_key} newer")),
+                    None => AttrValue::Static(position_key),
+                };
 
                 html! {
                     <>
@@ -186,51 +196,254 @@ impl Component for Index {
                                 </RouterLink>
                             </div>
                         </nav>
-                        <div class="feature-list">{ for list }</div>
+                        {self.view_filter(ctx)}
+                        <ExportButtons features={self.features.clone()}
+                            file_name={format!("caniuse-{}", position_key.replace(' ', "-"))} />
+                        <FeatureList features={self.features.clone()} selected={self.selected}
+                            position_key={position_key} />
                     </>
                 }
             }
             ContentsToRender::SearchResults => {
-                let list = self.current_search_results.iter().map(|&f| {
-                    html! { <FeatureEntry key={f.slug} data={f} /> }
-                });
+                let position_key = match &ctx.props().show {
+                    IndexContents::SearchResults { search_query } => {
+                        let filter = if self.newer_than().is_some() { " newer" } else { "" };
+                        Some(AttrValue::from(format!("search {search_query}{filter}")))
+                    }
+                    IndexContents::Explore(_) => None,
+                };
 
-                html! { <div class="feature-list">{ for list }</div> }
+                html! {
+                    <>
+                        {self.view_filter(ctx)}
+                        <ExportButtons features={self.features.clone()}
+                            file_name="caniuse-search-results" />
+                        <FeatureList features={self.features.clone()} selected={self.selected}
+                            position_key={position_key} />
+                        {self.view_weak_matches()}
+                    </>
+                }
             }
-            ContentsToRender::EmptySearchResults => {
-                html! { <div class="box muted">{"Nothing found, sorry."}</div> }
+            ContentsToRender::EmptySearchResults { suggestions, std_path } => {
+                let maybe_suggestions = if suggestions.is_empty() {
+                    html! {}
+                } else {
+                    let links = suggestions.iter().enumerate().map(|(i, &suggestion)| {
+                        let separator = if i == 0 { "" } else { ", " };
+                        let query = markup::to_plain(suggestion);
+                        html! { <>{separator}{search_link(&query, view_text(suggestion))}</> }
+                    });
+
+                    html! { <p>{"Did you mean "}{ for links }{"?"}</p> }
+                };
+
+                let maybe_rustdoc_link = match std_path {
+                    Some(path) => html! {
+                        <p>
+                            {"Search the standard library documentation for "}
+                            <a href={format!("https://doc.rust-lang.org/std/?search={path}")}>
+                                <code>{path}</code>
+                            </a>
+                        </p>
+                    },
+                    None => html! {},
+                };
+
+                html! {
+                    <>
+                        <div class="box">
+                            <p class="muted">{"Nothing found, sorry."}</p>
+                            {maybe_suggestions}
+                            {maybe_rustdoc_link}
+                        </div>
+                        {self.view_weak_matches()}
+                    </>
+                }
             }
             ContentsToRender::InvalidSearchResults => {
                 html! { <div class="box muted">{"Invalid search terms."}</div> }
             }
         }
     }
+
+    fn rendered(&mut self, ctx: &Context<Self>, _first_render: bool) {
+        match &ctx.props().show {
+            IndexContents::Explore(Explore::Stable) => set_title(None),
+            IndexContents::Explore(Explore::RecentlyStabilized) => {
+                set_title(Some("Recently stabilized"))
+            }
+            IndexContents::Explore(Explore::Unstable) => set_title(Some("Unstable features")),
+            IndexContents::SearchResults { search_query } => {
+                set_title(Some(&format!("Search: {search_query}")))
+            }
+        }
+    }
+}
+
+impl Index {
+    /// Recomputes the features to list, after the props or the filter changed.
+    fn refresh(&mut self, ctx: &Context<Self>) {
+        self.show = show(
+            ctx.props(),
+            &mut self.current_search_terms,
+            &mut self.current_search_results,
+            &mut self.search_scores,
+        );
+
+        let newer_than = self.newer_than();
+        if let Some(toolchain) = newer_than {
+            self.current_search_results.weak_matches.retain(|f| !f.is_available_in(toolchain));
+        }
+        self.features = list_features(&self.show, &self.current_search_results, newer_than);
+        self.selected = None;
+    }
+
+    /// The user's version, if only features newer than it are listed.
+    fn newer_than(&self) -> Option<RustVersion> {
+        self.user.toolchain.filter(|_| self.only_newer)
+    }
+
+    fn view_filter(&self, ctx: &Context<Self>) -> Html {
+        let Some(toolchain) = self.user.toolchain else {
+            return html! {};
+        };
+
+        let maybe_empty_note = if self.only_newer && self.num_entries() == 0 {
+            html! { <p class="muted">{"Nothing newer than your version here."}</p> }
+        } else {
+            html! {}
+        };
+
+        html! {
+            <div class="filters">
+                <label>
+                    <input type="checkbox" checked={self.only_newer}
+                        onchange={ctx.link().callback(|_| Msg::ToggleOnlyNewer)} />
+                    {"Only features newer than Rust "}{toolchain.to_string()}
+                </label>
+                {maybe_empty_note}
+            </div>
+        }
+    }
+
+    /// The number of feature entries currently rendered.
+    fn num_entries(&self) -> usize {
+        match &self.show {
+            ContentsToRender::Explore(_) => self.features.len(),
+            ContentsToRender::SearchResults | ContentsToRender::EmptySearchResults { .. } => {
+                self.features.len() + self.current_search_results.weak_matches.len()
+            }
+            ContentsToRender::InvalidSearchResults => 0,
+        }
+    }
+
+    fn view_weak_matches(&self) -> Html {
+        let weak_matches = &self.current_search_results.weak_matches;
+        if weak_matches.is_empty() {
+            return html! {};
+        }
+
+        // Keyboard selection continues from the regular matches
+        let list = weak_matches.iter().enumerate().map(|(i, &f)| {
+            let selected = self.selected == Some(self.features.len() + i);
+            html! { <FeatureEntry key={f.slug} data={f} selected={selected} /> }
+        });
+
+        html! {
+            <>
+                <h4 class="section-title muted">{"Weak matches"}</h4>
+                <div class="feature-list" role="list">{ for list }</div>
+            </>
+        }
+    }
 }
 
 fn show(
     props: &Props,
     current_search_terms: &mut Vec<String>,
-    current_search_results: &mut Vec<FeatureData>,
+    current_search_results: &mut SearchResults,
     search_scores: &mut [(u16, f64)],
 ) -> ContentsToRender {
     match &props.show {
         IndexContents::Explore(ex) => ContentsToRender::Explore(*ex),
-        IndexContents::SearchResults { search_query } => match extract_search_terms(search_query) {
-            Ok(search_terms) => {
-                *current_search_results = run_search(&search_terms, search_scores);
-                *current_search_terms = search_terms;
-
-                if current_search_results.is_empty() {
-                    ContentsToRender::EmptySearchResults
+        IndexContents::SearchResults { search_query } => {
+            if let Some(matches) = exact_matches(search_query) {
+                current_search_terms.clear();
+                *current_search_results = SearchResults { matches, weak_matches: Vec::new() };
+
+                return if current_search_results.matches.is_empty() {
+                    ContentsToRender::EmptySearchResults {
+                        suggestions: exact_suggestions(search_query),
+                        std_path: None,
+                    }
                 } else {
                     ContentsToRender::SearchResults
+                };
+            }
+
+            match extract_search_terms(search_query) {
+                Ok(search_terms) => {
+                    *current_search_results = run_search(&search_terms, search_scores);
+                    *current_search_terms = search_terms;
+
+                    if current_search_results.matches.is_empty() {
+                        ContentsToRender::EmptySearchResults {
+                            suggestions: suggestions(current_search_terms),
+                            std_path: std_path(current_search_terms).map(ToOwned::to_owned),
+                        }
+                    } else {
+                        ContentsToRender::SearchResults
+                    }
                 }
+                Err(InvalidSearchQuery) => ContentsToRender::InvalidSearchResults,
             }
-            Err(InvalidSearchQuery) => ContentsToRender::InvalidSearchResults,
-        },
+        }
+    }
+}
+
+fn list_features(
+    show: &ContentsToRender,
+    search_results: &SearchResults,
+    newer_than: Option<RustVersion>,
+) -> Rc<[FeatureData]> {
+    let is_listed = |f: &&FeatureData| newer_than.is_none_or(|t| !f.is_available_in(t));
+    match show {
+        ContentsToRender::Explore(ex) => explore_features(*ex).filter(is_listed).copied().collect(),
+        ContentsToRender::SearchResults | ContentsToRender::EmptySearchResults { .. } => {
+            search_results.matches.iter().filter(is_listed).copied().collect()
+        }
+        ContentsToRender::InvalidSearchResults => Rc::new([]),
     }
 }
 
+/// Features are sorted by version, with unstable ones being at the very end,
+/// so every explore tab shows one or two contiguous ranges of `FEATURES`.
+fn explore_features(ex: Explore) -> impl Iterator<Item = &'static FeatureData> {
+    fn range(pred: impl Fn(&FeatureData) -> bool) -> &'static [FeatureData] {
+        let start = FEATURES.iter().position(&pred).unwrap_or(FEATURES.len());
+        let len = FEATURES[start..].iter().take_while(|f| pred(f)).count();
+        &FEATURES[start..start + len]
+    }
+
+    // FEATURES is sorted by version from newest to oldest, so every part of a
+    // tab is a contiguous range of it
+    let stable = latest_stable();
+    // The version after the latest stable one, which is in beta
+    let next = VERSIONS.iter().rev().map(|v| v.number).find(|&n| Some(n) > stable);
+    let newer_than = |f: &FeatureData, other| f.version.is_some_and(|v| Some(v.number) > other);
+
+    let (first, second) = match ex {
+        Explore::Stable => (range(|f| f.version.is_some() && !newer_than(f, stable)), &[][..]),
+        Explore::RecentlyStabilized => (
+            range(|f| newer_than(f, stable) && !newer_than(f, next)),
+            range(|f| newer_than(f, next)),
+        ),
+        Explore::Unstable => (range(|f| f.version.is_none()), &[][..]),
+    };
+
+    first.iter().chain(second)
+}
+
 fn active_if(cond: bool) -> Classes {
     if cond {
         "active".into()
@@ -239,7 +452,16 @@ fn active_if(cond: bool) -> Classes {
     }
 }
 
-// Creates a timeout that lets the browser render the page before calling `fn update()`.
-fn create_timeout(scope: Scope<Index>) -> Timeout {
-    Timeout::new(0, move || scope.send_message(Msg::Update))
+/// Whether arrow keys move the selection, which is only the case while focus
+/// is on the search box, a feature entry or nothing in particular. Everywhere
+/// else they keep scrolling the page or changing the value of a control.
+fn is_navigating(event: &Event) -> bool {
+    match event.target().and_then(|t| t.dyn_into::<Element>().ok()) {
+        Some(elem) => {
+            is_search_box(event)
+                || elem.tag_name() == "BODY"
+                || elem.closest(".feature-entry").ok().flatten().is_some()
+        }
+        None => true,
+    }
 }
@@ -0,0 +1,125 @@
+use yew::{html, Component, Context, Html, Properties};
+use yew_router::scope_ext::RouterScopeExt;
+
+use crate::{
+    search::{closest_features, closest_versions},
+    util::{home_button, set_title, view_text, Void},
+    AppRoute, RouterLink,
+};
+
+#[derive(Clone, PartialEq)]
+pub enum Missing {
+    /// A path that doesn't match any route
+    Page,
+    Feature {
+        slug: String,
+    },
+    Version {
+        number: String,
+    },
+}
+
+#[derive(Clone, PartialEq, Properties)]
+pub struct Props {
+    #[prop_or(Missing::Page)]
+    pub missing: Missing,
+}
+
+pub struct NotFound;
+
+const MAX_SUGGESTIONS: usize = 5;
+
+impl Component for NotFound {
+    type Message = Void;
+    type Properties = Props;
+
+    fn create(_: &Context<Self>) -> Self {
+        Self
+    }
+
+    fn update(&mut self, _: &Context<Self>, void: Self::Message) -> bool {
+        match void {}
+    }
+
+    fn changed(&mut self, _: &Context<Self>, _old_props: &Self::Properties) -> bool {
+        true
+    }
+
+    fn view(&self, ctx: &Context<Self>) -> Html {
+        let (heading, message, suggestions) = match &ctx.props().missing {
+            Missing::Feature { slug } => (
+                "Feature not found",
+                html! { <>{"There is no feature with the slug "}<code>{slug}</code>{"."}</> },
+                feature_suggestions(slug),
+            ),
+            Missing::Version { number } => {
+                let suggestions = closest_versions(number, MAX_SUGGESTIONS).into_iter().map(|v| {
+                    html! {
+                        <li>
+                            <RouterLink to={AppRoute::Version { number: v.number.to_string() }}>
+                                {"Rust "}{v.number.to_string()}
+                            </RouterLink>
+                        </li>
+                    }
+                });
+
+                (
+                    "Version not found",
+                    html! { <>{"There is no Rust version "}<code>{number}</code>{"."}</> },
+                    suggestions.collect(),
+                )
+            }
+            Missing::Page => {
+                let path = ctx.link().location().map(|l| l.path().to_owned()).unwrap_or_default();
+                let last_segment = path.trim_end_matches('/').rsplit('/').next().unwrap_or("");
+
+                (
+                    "Page not found",
+                    html! { <>{"There is no page at "}<code>{path.clone()}</code>{"."}</> },
+                    feature_suggestions(last_segment),
+                )
+            }
+        };
+
+        let maybe_suggestions = if suggestions.is_empty() {
+            html! {}
+        } else {
+            html! {
+                <>
+                    <p>{"Did you mean one of these?"}</p>
+                    <ul class="suggestions">{ for suggestions }</ul>
+                </>
+            }
+        };
+
+        html! {
+            <>
+                {home_button()}
+                <div class="box">
+                    <h3 class="title">{heading}</h3>
+                    <p class="muted">{message}</p>
+                    {maybe_suggestions}
+                </div>
+            </>
+        }
+    }
+
+    fn rendered(&mut self, _: &Context<Self>, _first_render: bool) {
+        set_title(Some("Not found"));
+    }
+}
+
+fn feature_suggestions(slug: &str) -> Vec<Html> {
+    closest_features(slug, MAX_SUGGESTIONS)
+        .into_iter()
+        .map(|f| {
+            html! {
+                <li>
+                    <RouterLink to={AppRoute::Feature { name: f.slug.into() }}>
+                        {view_text(f.title)}
+                    </RouterLink>
+                </li>
+            }
+        })
+        .collect()
+}
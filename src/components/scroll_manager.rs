@@ -0,0 +1,62 @@
+use gloo_events::EventListener;
+use gloo_timers::callback::Timeout;
+use gloo_utils::window;
+use web_sys::ScrollRestoration;
+use yew::{html, Component, Context, Html};
+use yew_router::scope_ext::{LocationHandle, RouterScopeExt};
+
+use crate::scroll;
+
+/// Resets the scroll position when navigating to a new page, keeps it when
+/// the current history entry is replaced, and restores it when navigating
+/// back or forward.
+pub struct ScrollManager {
+    navigation_timeout: Option<Timeout>,
+
+    _location_listener: Option<LocationHandle>,
+    _popstate_listener: EventListener,
+    _scroll_listener: EventListener,
+}
+
+pub enum Msg {
+    LocationChanged,
+}
+
+impl Component for ScrollManager {
+    type Message = Msg;
+    type Properties = ();
+
+    fn create(ctx: &Context<Self>) -> Self {
+        if let Ok(history) = window().history() {
+            history.set_scroll_restoration(ScrollRestoration::Manual).unwrap();
+        }
+
+        // Only fired for back / forward navigation, not for pushing or
+        // replacing history entries
+        let _popstate_listener =
+            EventListener::new(&window(), "popstate", |_| scroll::start_restore());
+        let _scroll_listener = EventListener::new(&window(), "scroll", |_| scroll::save_position());
+        let _location_listener =
+            ctx.link().add_location_listener(ctx.link().callback(|_| Msg::LocationChanged));
+
+        Self { navigation_timeout: None, _location_listener, _popstate_listener, _scroll_listener }
+    }
+
+    fn update(&mut self, _: &Context<Self>, msg: Msg) -> bool {
+        match msg {
+            Msg::LocationChanged => {
+                // Let the browser render the new page before scrolling
+                self.navigation_timeout = Some(Timeout::new(0, scroll::finish_navigation));
+                false
+            }
+        }
+    }
+
+    fn changed(&mut self, _: &Context<Self>, _old_props: &Self::Properties) -> bool {
+        false
+    }
+
+    fn view(&self, _: &Context<Self>) -> Html {
+        html! {}
+    }
+}
@@ -0,0 +1,73 @@
+use web_sys::{HtmlElement, MouseEvent};
+use yew::{html, Callback, Component, Context, Html, NodeRef, Properties};
+
+use crate::util::Void;
+
+#[derive(Clone, PartialEq, Properties)]
+pub struct Props {
+    pub onclose: Callback<()>,
+}
+
+/// Overlay listing the available keyboard shortcuts.
+pub struct Shortcuts {
+    dialog_ref: NodeRef,
+}
+
+const SHORTCUTS: &[(&[&str], &str)] = &[
+    (&["s", "/"], "Focus the search box"),
+    (&["↓", "j"], "Select the next feature"),
+    (&["↑", "k"], "Select the previous feature"),
+    (&["Enter"], "Open the selected feature"),
+    (&["Esc"], "Clear the search"),
+    (&["Ctrl+V"], "Set your Rust version from a pasted rust-toolchain.toml or Cargo.toml"),
+    (&["?"], "Show / hide this help"),
+];
+
+impl Component for Shortcuts {
+    type Message = Void;
+    type Properties = Props;
+
+    fn create(_: &Context<Self>) -> Self {
+        Self { dialog_ref: NodeRef::default() }
+    }
+
+    fn update(&mut self, _: &Context<Self>, void: Self::Message) -> bool {
+        match void {}
+    }
+
+    fn changed(&mut self, _: &Context<Self>, _old_props: &Self::Properties) -> bool {
+        true
+    }
+
+    fn view(&self, ctx: &Context<Self>) -> Html {
+        let onclose = ctx.props().onclose.reform(|_: MouseEvent| ());
+        let stop_propagation = |ev: MouseEvent| ev.stop_propagation();
+
+        let rows = SHORTCUTS.iter().map(|&(keys, description)| {
+            let keys = keys.iter().map(|&key| html! { <kbd>{key}</kbd> });
+            html! {
+                <>
+                    <dt>{ for keys }</dt>
+                    <dd>{description}</dd>
+                </>
+            }
+        });
+
+        html! {
+            <div class="overlay" onclick={onclose}>
+                <div ref={self.dialog_ref.clone()} class="box shortcuts" role="dialog"
+                    aria-modal="true" aria-labelledby="shortcuts-title" tabindex="-1"
+                    onclick={stop_propagation}>
+                    <h3 id="shortcuts-title">{"Keyboard shortcuts"}</h3>
+                    <dl class="info">{ for rows }</dl>
+                </div>
+            </div>
+        }
+    }
+
+    fn rendered(&mut self, _: &Context<Self>, first_render: bool) {
+        if first_render {
+            self.dialog_ref.cast::<HtmlElement>().unwrap().focus().unwrap();
+        }
+    }
+}
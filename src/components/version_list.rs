@@ -0,0 +1,117 @@
+use std::collections::HashMap;
+
+use yew::{html, Component, Context, Html};
+
+use crate::{
+    util::{home_button, set_title, view_link, view_release_date, Void},
+    AppRoute, Channel, RouterLink, FEATURES, VERSIONS,
+};
+
+/// Release history, with all versions on a timeline.
+pub struct VersionList;
+
+impl Component for VersionList {
+    type Message = Void;
+    type Properties = ();
+
+    fn create(_: &Context<Self>) -> Self {
+        Self
+    }
+
+    fn update(&mut self, _: &Context<Self>, void: Self::Message) -> bool {
+        match void {}
+    }
+
+    fn changed(&mut self, _: &Context<Self>, _old_props: &Self::Properties) -> bool {
+        false
+    }
+
+    fn view(&self, _: &Context<Self>) -> Html {
+        let mut feature_counts = HashMap::new();
+        for v in FEATURES.iter().filter_map(|f| f.version) {
+            *feature_counts.entry(v.number).or_insert(0) += 1;
+        }
+
+        // VERSIONS is sorted from newest to oldest
+        let current_stable = VERSIONS.iter().find(|v| v.channel == Channel::Stable);
+
+        let mut last_year = None;
+        let entries = VERSIONS.iter().map(|v| {
+            // Versions without a release date are grouped with the newest year
+            let year = v.release_date.map(|date| &date[..4]);
+            let maybe_year_marker = match year {
+                Some(year) if last_year != Some(year) => {
+                    last_year = Some(year);
+                    html! { <li class="year">{year}</li> }
+                }
+                _ => html! {},
+            };
+
+            // The versions currently on the beta and nightly channels are
+            // highlighted along with the latest stable release
+            let (channel_class, channel_label) = match v.channel {
+                Channel::Nightly => ("nightly", "Nightly"),
+                Channel::Beta => ("beta", "Beta"),
+                Channel::Stable if current_stable == Some(v) => ("stable", "Stable"),
+                Channel::Stable => ("stable", ""),
+            };
+            let release_class =
+                if channel_label.is_empty() { "release" } else { "release current" };
+
+            let feature_count = match feature_counts.get(&v.number).copied().unwrap_or(0) {
+                1 => "1 feature".to_owned(),
+                n => format!("{n} features"),
+            };
+
+            let maybe_patch_releases = match v.patch_releases {
+                [] => html! {},
+                patch_releases => {
+                    let numbers: Vec<_> =
+                        patch_releases.iter().map(|p| p.number.to_string()).collect();
+                    html! {
+                        <>
+                            <span>{"Patch releases:"}</span>
+                            <span>{numbers.join(", ")}</span>
+                        </>
+                    }
+                }
+            };
+
+            html! {
+                <>
+                    {maybe_year_marker}
+                    <li class={release_class}>
+                        <div class="release-title">
+                            <RouterLink to={AppRoute::Version { number: v.number.to_string() }}>
+                                {"Rust "}{v.number.to_string()}
+                            </RouterLink>
+                            <span class={format!("channel {channel_class}")}>{channel_label}</span>
+                        </div>
+                        <div class="info">
+                            <span>{"Release date:"}</span>
+                            <span>{view_release_date(v)}</span>
+                            <span>{"Stabilized:"}</span>
+                            <span>{feature_count}</span>
+                            {maybe_patch_releases}
+                        </div>
+                        <ul class="links">{ for v.links.iter().map(view_link) }</ul>
+                    </li>
+                </>
+            }
+        });
+
+        html! {
+            <>
+                {home_button()}
+                <div class="box versions">
+                    <h3 class="title">{"Rust releases"}</h3>
+                    <ol class="timeline">{ for entries }</ol>
+                </div>
+            </>
+        }
+    }
+
+    fn rendered(&mut self, _: &Context<Self>, _first_render: bool) {
+        set_title(Some("Rust releases"));
+    }
+}
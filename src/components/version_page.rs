@@ -1,9 +1,10 @@
-use gloo_utils::window;
+use std::rc::Rc;
+
 use yew::{html, Component, Context, Html, Properties};
 
 use crate::{
-    components::FeatureEntry,
-    util::{home_button, maybe_link, Void},
+    components::{ExportButtons, FeatureEntry},
+    util::{home_button, set_title, view_link, view_release_date, Void},
     VersionData, FEATURES,
 };
 
@@ -19,11 +20,6 @@ impl Component for VersionPage {
     type Properties = Props;
 
     fn create(_: &Context<Self>) -> Self {
-        // Ugly hack because I don't want to write my own component that resets
-        // the scroll position on route change. See also
-        // https://github.com/yewstack/yew/issues/1099
-        window().scroll_to_with_x_and_y(0.0, 0.0);
-
         Self
     }
 
@@ -38,50 +34,66 @@ impl Component for VersionPage {
     fn view(&self, ctx: &Context<Self>) -> Html {
         let v = &ctx.props().data;
 
-        let maybe_blog_link =
-            maybe_link("Blog post", "https://blog.rust-lang.org/", v.blog_post_path);
-        let maybe_release_notes = maybe_link(
-            "Release notes",
-            "https://github.com/rust-lang/rust/blob/master/RELEASES.md#",
-            v.release_notes,
-        );
-        let maybe_gh_milestone_link = maybe_link(
-            "GitHub milestone",
-            "https://github.com/rust-lang/rust/milestone/",
-            v.gh_milestone_id,
-        );
-
         let maybe_release_date = match v.release_date {
-            Some(release_date) => html! {
+            Some(_) => html! {
                 <>
                     <span>{"Release date:"}</span>
-                    <time datetime={release_date}>{release_date}</time>
+                    {view_release_date(v)}
                 </>
             },
             None => html! {},
         };
 
-        let features = FEATURES
+        let patch_releases = v.patch_releases.iter().map(|p| {
+            let maybe_release_date = match p.release_date {
+                Some(release_date) => html! {
+                    <>
+                        <span>{"Release date:"}</span>
+                        <time datetime={release_date}>{release_date}</time>
+                    </>
+                },
+                None => html! {},
+            };
+
+            html! {
+                <div class="patch-release">
+                    <h4>{"Rust "}{p.number.to_string()}</h4>
+                    <div class="info">
+                        {maybe_release_date}
+                    </div>
+                    <ul class="links">{ for p.links.iter().map(view_link) }</ul>
+                </div>
+            }
+        });
+
+        let features: Rc<[_]> = FEATURES
             .iter()
             .filter(|f| matches!(f.version, Some(fv) if fv.number == v.number))
-            .map(|&f| html! { <FeatureEntry key={f.slug} data={f} show_version=false /> });
+            .copied()
+            .collect();
+        let entries = features.iter().map(|&f| {
+            html! { <FeatureEntry key={f.slug} data={f} show_version=false /> }
+        });
 
         html! {
             <>
                 {home_button()}
                 <div class="box">
-                    <h3 class="title">{"Rust "}{v.number}</h3>
+                    <h3 class="title">{"Rust "}{v.number.to_string()}</h3>
                     <div class="info">
                         {maybe_release_date}
                     </div>
-                    <ul class="links">
-                        {maybe_blog_link}
-                        {maybe_release_notes}
-                        {maybe_gh_milestone_link}
-                    </ul>
+                    <ul class="links">{ for v.links.iter().map(view_link) }</ul>
+                    { for patch_releases }
                 </div>
-                <div class="feature-list">{ for features }</div>
+                <ExportButtons features={features.clone()}
+                    file_name={format!("caniuse-rust-{}", v.number)} />
+                <div class="feature-list">{ for entries }</div>
             </>
         }
     }
+
+    fn rendered(&mut self, ctx: &Context<Self>, _first_render: bool) {
+        set_title(Some(&format!("Rust {}", ctx.props().data.number)));
+    }
 }
@@ -0,0 +1,81 @@
+use yew::{context::ContextHandle, html, Component, Context, Html};
+
+use crate::{
+    components::FeatureEntry,
+    icons::fa_star,
+    util::{home_button, set_title},
+    UserContext,
+};
+
+/// The features the user watches, with the ones that changed since the last
+/// visit listed first.
+pub struct WatchlistPage {
+    user: UserContext,
+
+    _user_handle: Option<ContextHandle<UserContext>>,
+}
+
+pub enum Msg {
+    UserChanged(UserContext),
+}
+
+impl Component for WatchlistPage {
+    type Message = Msg;
+    type Properties = ();
+
+    fn create(ctx: &Context<Self>) -> Self {
+        let (user, _user_handle) = UserContext::subscribe(ctx, Msg::UserChanged);
+        Self { user, _user_handle }
+    }
+
+    fn update(&mut self, _: &Context<Self>, msg: Msg) -> bool {
+        match msg {
+            Msg::UserChanged(user) => {
+                self.user = user;
+                true
+            }
+        }
+    }
+
+    fn changed(&mut self, _: &Context<Self>, _old_props: &Self::Properties) -> bool {
+        false
+    }
+
+    fn view(&self, _: &Context<Self>) -> Html {
+        let watchlist = &self.user.watchlist;
+
+        let summary = match watchlist.num_changes() {
+            _ if watchlist.is_empty() => html! {
+                <p>
+                    {"You aren't watching any features yet. Use the "}{fa_star()}
+                    {" button on a feature to add it here and see when it gets stabilized."}
+                </p>
+            },
+            0 => html! { <p class="muted">{"Nothing changed since your last visit."}</p> },
+            1 => html! { <p>{"1 feature changed since your last visit."}</p> },
+            n => html! { <p>{n}{" features changed since your last visit."}</p> },
+        };
+
+        // Stable sort, so features stay in the order they were added otherwise
+        let mut features: Vec<_> = watchlist.features().collect();
+        features.sort_by_key(|f| watchlist.previous_status(f.slug).is_none());
+        let entries = features.into_iter().map(|&f| {
+            html! { <FeatureEntry key={f.slug} data={f} /> }
+        });
+
+        html! {
+            <>
+                {home_button()}
+                <div class="box watchlist">
+                    <h3 class="title">{"Watchlist"}</h3>
+                    {summary}
+                </div>
+                <div class="feature-list" role="list">{ for entries }</div>
+            </>
+        }
+    }
+
+    fn rendered(&mut self, _: &Context<Self>, _first_render: bool) {
+        set_title(Some("Watchlist"));
+    }
+}
@@ -1,3 +1,5 @@
+use crate::{link::LinkKind, version::RustVersion};
+
 /// A "feature", as tracked by this app. Can be a nightly Rust feature, a
 /// stabilized API, or anything else that one version of Rust (deliberately)
 /// supports while a previous one didn't support it.
@@ -5,54 +7,87 @@
 pub struct FeatureData {
     /// Short description to identify the feature
     pub title: &'static str,
+    /// A paragraph about the feature, if it needs more explanation than the
+    /// title
+    pub description: Option<&'static str>,
     /// Feature flag name, for things that were previously or are still Rust
     /// nightly features with such a thing (`#![feature(...)]`)
     pub flag: Option<&'static str>,
     /// Feature slug, used for the permalink.
     pub slug: &'static str,
     pub version: Option<&'static VersionData>,
-    /// RFC id (https://github.com/rust-lang/rfcs/pull/{id})
-    pub rfc_id: Option<u64>,
-    /// Implementation PR id (https://github.com/rust-lang/rust/pull/{id})
-    ///
-    /// Only for small features that were implemented in one PR.
-    pub impl_pr_id: Option<u64>,
-    /// Tracking issue id (https://github.com/rust-lang/rust/issues/{id})
-    pub tracking_issue_id: Option<u64>,
-    /// Stabilization PR id (https://github.com/rust-lang/rust/pull/{id})
-    pub stabilization_pr_id: Option<u64>,
-    /// Documentation path (https://doc.rust-lang.org/{path})
-    pub doc_path: Option<&'static str>,
-    /// Edition guide path (https://doc.rust-lang.org/edition-guide/{path})
-    pub edition_guide_path: Option<&'static str>,
-    /// Unstable book path (https://doc.rust-lang.org/unstable-book/{path})
-    pub unstable_book_path: Option<&'static str>,
+    /// RFC, PRs, tracking issue, documentation and so on
+    pub links: &'static [Link],
     /// Language items (functions, structs, modules) that are part of this
     /// feature (unless this feature is exactly one item and that item is
     /// already used as the title)
-    pub items: &'static [&'static str],
+    pub items: &'static [Item],
+    /// Aliases (alternatives to the title of this feature)
+    pub aliases: &'static [&'static str],
 }
 
 impl FeatureData {
-    pub fn is_on_channel(&self, chan: Channel) -> bool {
-        self.version.map(|v| v.channel == chan).unwrap_or(false)
+    /// Whether this feature can be used with the given version of Rust.
+    pub fn is_available_in(&self, version: RustVersion) -> bool {
+        self.version.is_some_and(|v| v.number <= version)
     }
 }
 
+/// Finds the version with the given number. For `x.y.0` and other patch
+/// releases, this is the `x.y` version they belong to.
+pub fn find_version(number: RustVersion) -> Option<&'static VersionData> {
+    VERSIONS.iter().find(|v| v.number == number.without_patch()).filter(|v| match number.patch {
+        None | Some(0) => true,
+        Some(_) => v.patch_releases.iter().any(|p| p.number == number),
+    })
+}
+
+/// The newest version that is available on the stable channel.
+pub fn latest_stable() -> Option<RustVersion> {
+    // VERSIONS is sorted from newest to oldest
+    VERSIONS.iter().find(|v| v.channel == Channel::Stable).map(|v| v.number)
+}
+
 #[derive(Copy, Clone, Debug, PartialEq)]
 pub struct VersionData {
-    /// The version number, without the patch component (e.g. "1.31")
-    pub number: &'static str,
+    /// The version number, without the patch component (e.g. 1.31)
+    pub number: RustVersion,
     /// The channel (stable / beta / nightly)
     pub channel: Channel,
     /// Release date, in format "yyyy-mm-dd"
     pub release_date: Option<&'static str>,
-    /// Release notes (https://github.com/rust-lang/rust/blob/master/RELEASES.md#{anchor})
-    pub release_notes: Option<&'static str>,
-    /// GitHub milestone id (https://github.com/rust-lang/rust/milestone/{id})
-    pub gh_milestone_id: Option<u64>,
-    /// Blog post path (https://blog.rust-lang.org/{path})
-    pub blog_post_path: Option<&'static str>,
+    /// Whether the release date is projected from the six-week release cycle
+    /// rather than known
+    pub release_date_estimated: bool,
+    /// Blog post, release notes and GitHub milestone
+    pub links: &'static [Link],
+    /// Patch releases of this version, oldest first
+    pub patch_releases: &'static [PatchRelease],
+}
+
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub struct PatchRelease {
+    /// The full version number (e.g. 1.52.1)
+    pub number: RustVersion,
+    /// Release date, in format "yyyy-mm-dd"
+    pub release_date: Option<&'static str>,
+    /// Blog post and release notes
+    pub links: &'static [Link],
+}
+
+/// A link to another site, see [`LinkKind`] for what it can point to.
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub struct Link {
+    pub kind: LinkKind,
+    pub url: &'static str,
+}
+
+/// A language item, with a link to its documentation if it is written as a
+/// path.
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub struct Item {
+    pub code: &'static str,
+    pub url: Option<&'static str>,
 }
 
 #[derive(Copy, Clone, Debug, PartialEq)]
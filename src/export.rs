@@ -0,0 +1,247 @@
+//! Exporting tables as files for download.
+
+use std::{collections::BTreeSet, fmt::Write as _};
+
+use gloo_timers::callback::Timeout;
+use gloo_utils::document;
+use js_sys::Array;
+use wasm_bindgen::{JsCast, JsValue};
+use web_sys::{Blob, BlobPropertyBag, HtmlAnchorElement, Url};
+
+use crate::{markup, FeatureData};
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum Format {
+    Csv,
+    Markdown,
+    Json,
+}
+
+impl Format {
+    pub fn extension(self) -> &'static str {
+        match self {
+            Format::Csv => "csv",
+            Format::Markdown => "md",
+            Format::Json => "json",
+        }
+    }
+
+    pub fn mime_type(self) -> &'static str {
+        match self {
+            Format::Csv => "text/csv",
+            Format::Markdown => "text/markdown",
+            Format::Json => "application/json",
+        }
+    }
+
+    /// Formats the table, see the `to_*` methods of [`Table`].
+    pub fn write(self, table: &Table) -> String {
+        match self {
+            Format::Csv => table.to_csv(),
+            Format::Markdown => table.to_markdown(),
+            Format::Json => table.to_json(),
+        }
+    }
+}
+
+/// A table of text cells with a header row.
+pub struct Table {
+    pub header: Vec<String>,
+    pub rows: Vec<Vec<String>>,
+}
+
+impl Table {
+    /// Formats the table as CSV, quoting cells as described in RFC 4180. It
+    /// starts with a byte order mark, without which Excel assumes a legacy
+    /// encoding.
+    pub fn to_csv(&self) -> String {
+        fn write_row(out: &mut String, row: &[String]) {
+            for (i, cell) in row.iter().enumerate() {
+                if i != 0 {
+                    out.push(',');
+                }
+                if cell.contains([',', '"', '\n', '\r']) {
+                    out.push('"');
+                    out.push_str(&cell.replace('"', "\"\""));
+                    out.push('"');
+                } else {
+                    out.push_str(cell);
+                }
+            }
+            out.push_str("\r\n");
+        }
+
+        let mut out = String::from('\u{feff}');
+        write_row(&mut out, &self.header);
+        for row in &self.rows {
+            write_row(&mut out, row);
+        }
+        out
+    }
+
+    /// Formats the table as a GitHub Flavored Markdown table.
+    pub fn to_markdown(&self) -> String {
+        fn write_row<'a>(out: &mut String, row: impl IntoIterator<Item = &'a str>) {
+            out.push('|');
+            for cell in row {
+                out.push(' ');
+                out.push_str(&cell.replace('|', "\\|").replace('\n', " "));
+                out.push_str(" |");
+            }
+            out.push('\n');
+        }
+
+        let mut out = String::new();
+        write_row(&mut out, self.header.iter().map(String::as_str));
+        write_row(&mut out, self.header.iter().map(|_| "---"));
+        for row in &self.rows {
+            write_row(&mut out, row.iter().map(String::as_str));
+        }
+        out
+    }
+
+    /// Formats the table as a JSON array with one object per row. The keys
+    /// are the lowercased header cells with spaces replaced by underscores,
+    /// and empty cells become `null`.
+    pub fn to_json(&self) -> String {
+        fn write_string(out: &mut String, s: &str) {
+            out.push('"');
+            for c in s.chars() {
+                match c {
+                    '"' => out.push_str("\\\""),
+                    '\\' => out.push_str("\\\\"),
+                    '\n' => out.push_str("\\n"),
+                    '\r' => out.push_str("\\r"),
+                    '\t' => out.push_str("\\t"),
+                    c if c.is_control() => write!(out, "\\u{:04x}", c as u32).unwrap(),
+                    c => out.push(c),
+                }
+            }
+            out.push('"');
+        }
+
+        let keys: Vec<_> = self.header.iter().map(|h| h.to_lowercase().replace(' ', "_")).collect();
+
+        let mut out = String::from("[");
+        for (i, row) in self.rows.iter().enumerate() {
+            out.push_str(if i == 0 { "\n  {" } else { ",\n  {" });
+            for (j, (key, cell)) in keys.iter().zip(row).enumerate() {
+                if j != 0 {
+                    out.push_str(", ");
+                }
+                write_string(&mut out, key);
+                out.push_str(": ");
+                if cell.is_empty() {
+                    out.push_str("null");
+                } else {
+                    write_string(&mut out, cell);
+                }
+            }
+            out.push('}');
+        }
+        out.push_str(if self.rows.is_empty() { "]\n" } else { "\n]\n" });
+        out
+    }
+}
+
+/// A table of features with their version, feature flag and links, with a
+/// column for every kind of link any of the features has. Markdown keeps the
+/// markup in titles and flags.
+pub fn feature_table(features: &[FeatureData], markdown: bool) -> Table {
+    let text = |s: &str| if markdown { s.to_owned() } else { markup::to_plain(s) };
+    let link_kinds: BTreeSet<_> = features.iter().flat_map(|f| f.links).map(|l| l.kind).collect();
+
+    let header = ["Title", "Slug", "Version", "Flag", "URL"]
+        .into_iter()
+        .chain(link_kinds.iter().map(|k| k.label()))
+        .map(ToOwned::to_owned)
+        .collect();
+    let rows = features
+        .iter()
+        .map(|f| {
+            let links = link_kinds.iter().map(|&kind| {
+                let link = f.links.iter().find(|l| l.kind == kind);
+                link.map(|l| l.url.to_owned()).unwrap_or_default()
+            });
+            [
+                text(f.title),
+                f.slug.to_owned(),
+                f.version.map_or_else(|| "unstable".to_owned(), |v| v.number.to_string()),
+                f.flag.map(text).unwrap_or_default(),
+                format!("https://caniuse.rs/features/{}", f.slug),
+            ]
+            .into_iter()
+            .chain(links)
+            .collect()
+        })
+        .collect();
+
+    Table { header, rows }
+}
+
+/// Makes the browser download `contents` as a file named `file_name`.
+pub fn download(file_name: &str, mime_type: &str, contents: &str) {
+    let options = BlobPropertyBag::new();
+    options.set_type(mime_type);
+    let parts = Array::of1(&JsValue::from_str(contents));
+    let blob = Blob::new_with_str_sequence_and_options(&parts, &options).unwrap();
+    let url = Url::create_object_url_with_blob(&blob).unwrap();
+
+    let anchor: HtmlAnchorElement = document().create_element("a").unwrap().unchecked_into();
+    anchor.set_href(&url);
+    anchor.set_download(file_name);
+    anchor.click();
+
+    // Revoking the URL right away cancels the download in some browsers, as
+    // they only start it once the current task is done
+    Timeout::new(1_000, move || Url::revoke_object_url(&url).unwrap()).forget();
+}
+
+#[cfg(test)]
+mod tests {
+    use super::Table;
+
+    fn table() -> Table {
+        Table {
+            header: vec!["Feature".to_owned(), "Since".to_owned()],
+            rows: vec![
+                vec!["`let` chains, \"finally\"".to_owned(), "1.88".to_owned()],
+                vec!["a | b".to_owned(), "unstable".to_owned()],
+            ],
+        }
+    }
+
+    #[test]
+    fn csv() {
+        assert_eq!(
+            table().to_csv(),
+            "\u{feff}Feature,Since\r\n\"`let` chains, \"\"finally\"\"\",1.88\r\na | b,unstable\r\n"
+        );
+    }
+
+    #[test]
+    fn json() {
+        let mut table = table();
+        table.rows[1][1] = String::new();
+        assert_eq!(
+            table.to_json(),
+            "[\n  \
+             {\"feature\": \"`let` chains, \\\"finally\\\"\", \"since\": \"1.88\"},\n  \
+             {\"feature\": \"a | b\", \"since\": null}\n]\n"
+        );
+
+        let empty = Table { header: vec!["Feature".to_owned()], rows: Vec::new() };
+        assert_eq!(empty.to_json(), "[]\n");
+    }
+
+    #[test]
+    fn markdown() {
+        assert_eq!(
+            table().to_markdown(),
+            "| Feature | Since |\n\
+             | --- | --- |\n\
+             | `let` chains, \"finally\" | 1.88 |\n\
+             | a \\| b | unstable |\n"
+        );
+    }
+}
@@ -1,29 +1,88 @@
 #![allow(clippy::derive_partial_eq_without_eq)]
 
+use std::rc::Rc;
+
+#[cfg(feature = "app")]
 use gloo_utils::document;
+#[cfg(feature = "app")]
 use wasm_bindgen::prelude::wasm_bindgen;
+use yew::{context::ContextHandle, Callback, Component, Context};
 use yew_router::Routable;
 
+// The widget only needs part of the modules it shares with the app
+#[cfg_attr(not(feature = "app"), allow(dead_code))]
 mod data;
+#[cfg(feature = "app")]
+mod export;
+#[cfg_attr(not(feature = "app"), allow(dead_code))]
 mod icons;
+// Building and checking URLs only happens in the build script
+#[allow(dead_code)]
+mod link;
+mod markup;
+// Only used by the build script, compiled here for its tests
+#[cfg(test)]
+mod rustdoc;
+#[cfg(feature = "app")]
+mod scroll;
+#[cfg(feature = "app")]
 mod search;
+#[cfg(feature = "app")]
+mod toolchain;
+#[cfg_attr(not(feature = "app"), allow(dead_code))]
 mod util;
+#[cfg_attr(not(feature = "app"), allow(dead_code))]
+mod version;
+#[cfg_attr(not(feature = "app"), allow(dead_code))]
+mod watchlist;
+#[cfg(feature = "widget")]
+mod widget;
 mod components {
+    #[cfg(feature = "app")]
     mod about;
+    #[cfg(feature = "app")]
     mod app;
+    #[cfg(feature = "app")]
+    mod compare;
+    #[cfg(feature = "app")]
+    mod export_buttons;
     mod feature_entry;
+    #[cfg(feature = "app")]
+    mod feature_list;
+    #[cfg(feature = "app")]
     mod feature_page;
+    #[cfg(feature = "app")]
     mod header;
+    #[cfg(feature = "app")]
     mod index;
+    #[cfg(feature = "app")]
+    mod not_found;
+    #[cfg(feature = "app")]
+    mod scroll_manager;
+    #[cfg(feature = "app")]
+    mod shortcuts;
+    #[cfg(feature = "app")]
+    mod version_list;
+    #[cfg(feature = "app")]
     mod version_page;
+    #[cfg(feature = "app")]
+    mod watchlist_page;
 
+    pub use self::feature_entry::FeatureEntry;
+    #[cfg(feature = "app")]
     pub use self::{
-        about::About, app::App, feature_entry::FeatureEntry, feature_page::FeaturePage,
-        header::Header, index::Index, version_page::VersionPage,
+        about::About, app::App, compare::Compare, export_buttons::ExportButtons,
+        feature_list::FeatureList, feature_page::FeaturePage, header::Header, index::Index,
+        not_found::NotFound, scroll_manager::ScrollManager, shortcuts::Shortcuts,
+        version_list::VersionList, version_page::VersionPage, watchlist_page::WatchlistPage,
     };
 }
 
-use data::{Channel, FeatureData, VersionData, FEATURES, VERSIONS};
+#[cfg(feature = "app")]
+use data::{Channel, VERSIONS};
+use data::{FeatureData, Link, VersionData, FEATURES};
+use version::RustVersion;
+use watchlist::Watchlist;
 
 #[derive(Clone, Debug, PartialEq, Routable)]
 enum AppRoute {
@@ -31,6 +90,12 @@ enum AppRoute {
     Feature { name: String },
     #[at("/versions/:number")]
     Version { number: String },
+    #[at("/versions")]
+    Versions,
+    #[at("/compare")]
+    Compare,
+    #[at("/watchlist")]
+    Watchlist,
     #[at("/about")]
     About,
     #[at("/recent")]
@@ -39,10 +104,36 @@ enum AppRoute {
     Unstable,
     #[at("/")]
     Index,
+    #[not_found]
+    #[at("/404")]
+    NotFound,
 }
 
 type RouterLink = yew_router::components::Link<AppRoute>;
 
+/// What the user set up for themselves, provided by `App` to every page. The
+/// widget has no provider and gets the default.
+#[derive(Clone, Default, PartialEq)]
+struct UserContext {
+    /// The user's version of Rust, if set
+    toolchain: Option<RustVersion>,
+    watchlist: Rc<Watchlist>,
+    onwatchtoggle: Callback<&'static str>,
+}
+
+impl UserContext {
+    /// Reads the context, with `msg` being sent to the component whenever it
+    /// changes for as long as the returned handle is kept.
+    fn subscribe<C: Component>(
+        ctx: &Context<C>,
+        msg: impl Fn(Self) -> C::Message + 'static,
+    ) -> (Self, Option<ContextHandle<Self>>) {
+        let (user, handle) = ctx.link().context(ctx.link().callback(msg)).unzip();
+        (user.unwrap_or_default(), handle)
+    }
+}
+
+#[cfg(feature = "app")]
 #[wasm_bindgen]
 pub fn run() {
     let page = document().query_selector("main").unwrap().unwrap();
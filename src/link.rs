@@ -0,0 +1,200 @@
+//! Links to other sites about a feature or version.
+//!
+//! The data files only contain the variable part of a link, like an issue
+//! number or a documentation path. The build script turns them into full
+//! URLs, so the views just render a list of [`LinkKind`]s with their URL.
+
+use std::fmt;
+
+/// What a link points to. Determines the label of the link, how its URL is
+/// built and which targets are valid.
+#[derive(Copy, Clone, Debug, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum LinkKind {
+    /// RFC, by the number of the PR that added it
+    Rfc,
+    /// The one PR that implemented a small feature
+    ImplPr,
+    TrackingIssue,
+    /// Comment that starts the final comment period or otherwise decides on
+    /// stabilizing the feature
+    FcpComment,
+    StabilizationPr,
+    /// Thread on Zulip or internals.rust-lang.org
+    Discussion,
+    /// Path on doc.rust-lang.org, including the channel directory if any
+    Documentation,
+    Reference,
+    EditionGuide,
+    UnstableBook,
+    BlogPost,
+    /// Anchor in RELEASES.md
+    ReleaseNotes,
+    GhMilestone,
+}
+
+/// What the variable part of a link is.
+enum Target {
+    /// A GitHub issue, PR or milestone number
+    Number,
+    /// A path relative to the base URL
+    Path,
+    /// A fragment identifier, without the `#`
+    Anchor,
+    /// A full URL on one of the given sites
+    Url(&'static [&'static str]),
+}
+
+impl LinkKind {
+    /// Text of the link.
+    pub const fn label(self) -> &'static str {
+        match self {
+            Self::Rfc => "RFC",
+            Self::ImplPr => "Implementation PR",
+            Self::TrackingIssue => "Tracking issue",
+            Self::FcpComment => "FCP comment",
+            Self::StabilizationPr => "Stabilization PR",
+            Self::Discussion => "Discussion",
+            Self::Documentation => "Documentation",
+            Self::Reference => "Reference",
+            Self::EditionGuide => "Edition Guide",
+            Self::UnstableBook => "Unstable book",
+            Self::BlogPost => "Blog post",
+            Self::ReleaseNotes => "Release notes",
+            Self::GhMilestone => "GitHub milestone",
+        }
+    }
+
+    /// Identifier of the link kind in `features.json`.
+    pub const fn key(self) -> &'static str {
+        match self {
+            Self::Rfc => "rfc",
+            Self::ImplPr => "impl_pr",
+            Self::TrackingIssue => "tracking_issue",
+            Self::FcpComment => "fcp_comment",
+            Self::StabilizationPr => "stabilization_pr",
+            Self::Discussion => "discussion",
+            Self::Documentation => "documentation",
+            Self::Reference => "reference",
+            Self::EditionGuide => "edition_guide",
+            Self::UnstableBook => "unstable_book",
+            Self::BlogPost => "blog_post",
+            Self::ReleaseNotes => "release_notes",
+            Self::GhMilestone => "gh_milestone",
+        }
+    }
+
+    const fn base_url(self) -> &'static str {
+        match self {
+            Self::Rfc => "https://github.com/rust-lang/rfcs/pull/",
+            Self::ImplPr | Self::StabilizationPr => "https://github.com/rust-lang/rust/pull/",
+            Self::TrackingIssue => "https://github.com/rust-lang/rust/issues/",
+            Self::FcpComment | Self::Discussion => "",
+            Self::Documentation => "https://doc.rust-lang.org/",
+            Self::Reference => "https://doc.rust-lang.org/reference/",
+            Self::EditionGuide => "https://doc.rust-lang.org/edition-guide/",
+            Self::UnstableBook => "https://doc.rust-lang.org/unstable-book/",
+            Self::BlogPost => "https://blog.rust-lang.org/",
+            Self::ReleaseNotes => "https://github.com/rust-lang/rust/blob/master/RELEASES.md#",
+            Self::GhMilestone => "https://github.com/rust-lang/rust/milestone/",
+        }
+    }
+
+    const fn target(self) -> Target {
+        match self {
+            Self::Rfc
+            | Self::ImplPr
+            | Self::TrackingIssue
+            | Self::StabilizationPr
+            | Self::GhMilestone => Target::Number,
+            Self::Documentation
+            | Self::Reference
+            | Self::EditionGuide
+            | Self::UnstableBook
+            | Self::BlogPost => Target::Path,
+            Self::ReleaseNotes => Target::Anchor,
+            Self::FcpComment => Target::Url(&["https://github.com/rust-lang/"]),
+            Self::Discussion => Target::Url(&[
+                "https://rust-lang.zulipchat.com/",
+                "https://internals.rust-lang.org/",
+            ]),
+        }
+    }
+
+    /// Builds the URL of a link to `target`, after checking that it is valid
+    /// for this kind of link.
+    pub fn url(self, target: &str) -> Result<String, InvalidLinkTarget> {
+        let valid = !target.is_empty()
+            && match self.target() {
+                Target::Number => target.bytes().all(|b| b.is_ascii_digit()),
+                // Full URLs and absolute paths would not be relative to the
+                // base URL
+                Target::Path => {
+                    !target.starts_with('/')
+                        && !target.contains("//")
+                        && !target.contains(char::is_whitespace)
+                }
+                Target::Anchor => {
+                    !target.contains(['#', '/']) && !target.contains(char::is_whitespace)
+                }
+                Target::Url(sites) => {
+                    sites.iter().any(|site| target.len() > site.len() && target.starts_with(site))
+                        && !target.contains(char::is_whitespace)
+                }
+            };
+
+        if valid {
+            Ok(format!("{}{target}", self.base_url()))
+        } else {
+            Err(InvalidLinkTarget { kind: self, target: target.to_owned() })
+        }
+    }
+}
+
+#[derive(Debug)]
+pub struct InvalidLinkTarget {
+    kind: LinkKind,
+    target: String,
+}
+
+impl fmt::Display for InvalidLinkTarget {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "invalid {} link `{}`, expected ", self.kind.key(), self.target)?;
+        match self.kind.target() {
+            Target::Number => f.write_str("a number"),
+            Target::Path => write!(f, "a path relative to {}", self.kind.base_url()),
+            Target::Anchor => write!(f, "an anchor in {}", self.kind.base_url()),
+            Target::Url(sites) => write!(f, "a URL starting with {}", sites.join(" or ")),
+        }
+    }
+}
+
+impl std::error::Error for InvalidLinkTarget {}
+
+#[cfg(test)]
+mod tests {
+    use super::LinkKind;
+
+    #[test]
+    fn url() {
+        assert_eq!(
+            LinkKind::Rfc.url("2497").unwrap(),
+            "https://github.com/rust-lang/rfcs/pull/2497"
+        );
+        assert_eq!(
+            LinkKind::Documentation.url("nightly/std/primitive.never.html").unwrap(),
+            "https://doc.rust-lang.org/nightly/std/primitive.never.html"
+        );
+
+        assert!(LinkKind::TrackingIssue.url("#53667").is_err());
+        assert!(LinkKind::BlogPost.url("https://blog.rust-lang.org/2022/11/03/").is_err());
+        assert!(LinkKind::EditionGuide.url("/rust-2021/").is_err());
+        assert!(LinkKind::Documentation.url("nightly//std/").is_err());
+        assert!(LinkKind::ReleaseNotes.url("#version-1650-2022-11-03").is_err());
+        assert!(LinkKind::UnstableBook.url("").is_err());
+
+        let zulip = "https://rust-lang.zulipchat.com/#narrow/stream/213817-t-lang/topic/let-else";
+        assert_eq!(LinkKind::Discussion.url(zulip).unwrap(), zulip);
+        assert!(LinkKind::Discussion.url("https://internals.rust-lang.org/").is_err());
+        assert!(LinkKind::FcpComment.url("https://example.com/rust-lang/").is_err());
+    }
+}
@@ -0,0 +1,213 @@
+//! The bit of Markdown that titles and descriptions can use: `code`,
+//! *emphasis*, **strong emphasis** and [links](https://example.com).
+//!
+//! Everything else, including markers without a matching closing one, is
+//! shown as written.
+
+#[derive(Debug, PartialEq)]
+pub enum Inline<'a> {
+    Text(&'a str),
+    Code(&'a str),
+    Emphasis(Vec<Inline<'a>>),
+    Strong(Vec<Inline<'a>>),
+    /// Only absolute `https://` URLs and paths on this site are linked
+    Link {
+        content: Vec<Inline<'a>>,
+        url: &'a str,
+    },
+}
+
+pub fn parse(text: &str) -> Vec<Inline<'_>> {
+    parse_inner(text, true)
+}
+
+/// Parses `text`, without links if `links` is false because it is already
+/// part of a link.
+fn parse_inner(text: &str, links: bool) -> Vec<Inline<'_>> {
+    let mut res = Vec::new();
+    let mut text_start = 0;
+    let mut pos = 0;
+
+    while let Some(c) = text[pos..].chars().next() {
+        match parse_marked(&text[pos..], links) {
+            Some((inline, len)) => {
+                if text_start < pos {
+                    res.push(Inline::Text(&text[text_start..pos]));
+                }
+                res.push(inline);
+                pos += len;
+                text_start = pos;
+            }
+            None => pos += c.len_utf8(),
+        }
+    }
+
+    if text_start < text.len() {
+        res.push(Inline::Text(&text[text_start..]));
+    }
+    res
+}
+
+/// Parses the marked up text `rest` starts with, if any. Returns it along
+/// with its length including the markers.
+fn parse_marked(rest: &str, links: bool) -> Option<(Inline<'_>, usize)> {
+    if let Some(after) = rest.strip_prefix('`') {
+        let end = after.find('`')?;
+        return Some((Inline::Code(&after[..end]), end + 2));
+    }
+    if let Some(after) = rest.strip_prefix("**") {
+        let content = emphasized(after, "**")?;
+        return Some((Inline::Strong(parse_inner(content, links)), content.len() + 4));
+    }
+    if let Some(after) = rest.strip_prefix('*') {
+        let content = emphasized(after, "*")?;
+        return Some((Inline::Emphasis(parse_inner(content, links)), content.len() + 2));
+    }
+    if let Some(after) = rest.strip_prefix('[').filter(|_| links) {
+        let (content, after) = after.split_once("](")?;
+        let (url, _) = after.split_once(')')?;
+        let valid_url = (url.starts_with("https://") || url.starts_with('/'))
+            && !url.contains(char::is_whitespace);
+        if content.is_empty() || !valid_url {
+            return None;
+        }
+        let len = content.len() + url.len() + 4;
+        return Some((Inline::Link { content: parse_inner(content, false), url }, len));
+    }
+    None
+}
+
+/// Finds the text up to the closing `marker`. Like in Markdown, emphasis has
+/// to start and end right next to the text, so `2 * 3 * 4` stays as it is.
+fn emphasized<'a>(after: &'a str, marker: &str) -> Option<&'a str> {
+    let content = &after[..after.find(marker)?];
+    let trimmed = !content.is_empty()
+        && !content.starts_with(char::is_whitespace)
+        && !content.ends_with(char::is_whitespace);
+    trimmed.then_some(content)
+}
+
+// Only used by the build script
+#[allow(dead_code)]
+pub fn contains_link(inlines: &[Inline<'_>]) -> bool {
+    inlines.iter().any(|inline| match inline {
+        Inline::Text(_) | Inline::Code(_) => false,
+        Inline::Emphasis(content) | Inline::Strong(content) => contains_link(content),
+        Inline::Link { .. } => true,
+    })
+}
+
+/// Renders `text` to HTML, for the static pages.
+#[allow(dead_code)]
+pub fn to_html(text: &str) -> String {
+    fn write_html(out: &mut String, inlines: &[Inline<'_>]) {
+        for inline in inlines {
+            match inline {
+                Inline::Text(text) => escape_html(out, text),
+                Inline::Code(code) => {
+                    out.push_str("<code>");
+                    escape_html(out, code);
+                    out.push_str("</code>");
+                }
+                Inline::Emphasis(content) => {
+                    out.push_str("<em>");
+                    write_html(out, content);
+                    out.push_str("</em>");
+                }
+                Inline::Strong(content) => {
+                    out.push_str("<strong>");
+                    write_html(out, content);
+                    out.push_str("</strong>");
+                }
+                Inline::Link { content, url } => {
+                    out.push_str("<a href=\"");
+                    escape_html(out, url);
+                    out.push_str("\">");
+                    write_html(out, content);
+                    out.push_str("</a>");
+                }
+            }
+        }
+    }
+
+    let mut out = String::new();
+    write_html(&mut out, &parse(text));
+    out
+}
+
+/// The text without any markup, for page titles and meta tags.
+pub fn to_plain(text: &str) -> String {
+    fn write_plain(out: &mut String, inlines: &[Inline<'_>]) {
+        for inline in inlines {
+            match inline {
+                Inline::Text(text) | Inline::Code(text) => out.push_str(text),
+                Inline::Emphasis(content)
+                | Inline::Strong(content)
+                | Inline::Link { content, .. } => write_plain(out, content),
+            }
+        }
+    }
+
+    let mut out = String::new();
+    write_plain(&mut out, &parse(text));
+    out
+}
+
+fn escape_html(out: &mut String, text: &str) {
+    for c in text.chars() {
+        match c {
+            '&' => out.push_str("&amp;"),
+            '<' => out.push_str("&lt;"),
+            '>' => out.push_str("&gt;"),
+            '"' => out.push_str("&quot;"),
+            '\'' => out.push_str("&#39;"),
+            _ => out.push(c),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{parse, to_html, to_plain, Inline::*};
+
+    #[test]
+    fn inlines() {
+        assert_eq!(
+            parse("`let` *else* **now** [stable](https://blog.rust-lang.org/)"),
+            [
+                Code("let"),
+                Text(" "),
+                Emphasis(vec![Text("else")]),
+                Text(" "),
+                Strong(vec![Text("now")]),
+                Text(" "),
+                Link { content: vec![Text("stable")], url: "https://blog.rust-lang.org/" },
+            ]
+        );
+        assert_eq!(
+            parse("[`Vec<T>`](/versions/1.0) [](/x)"),
+            [Link { content: vec![Code("Vec<T>")], url: "/versions/1.0" }, Text(" [](/x)"),]
+        );
+    }
+
+    #[test]
+    fn unbalanced() {
+        assert_eq!(parse("`From<bool> for floats"), [Text("`From<bool> for floats")]);
+        assert_eq!(parse("2 * 3 * 4"), [Text("2 * 3 * 4")]);
+        assert_eq!(
+            parse("[no link](javascript:alert(1))"),
+            [Text("[no link](javascript:alert(1))")]
+        );
+    }
+
+    #[test]
+    fn html_and_plain() {
+        let text = "`<*const T>::cast` & *[more](https://doc.rust-lang.org/?a=\"b\")*";
+        assert_eq!(
+            to_html(text),
+            "<code>&lt;*const T&gt;::cast</code> &amp; \
+             <em><a href=\"https://doc.rust-lang.org/?a=&quot;b&quot;\">more</a></em>"
+        );
+        assert_eq!(to_plain(text), "<*const T>::cast & more");
+    }
+}
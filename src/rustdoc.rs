@@ -0,0 +1,475 @@
+//! Where the items of a feature are documented.
+//!
+//! Items that are written as paths, like `std::thread::scope`,
+//! `NonZeroU8::checked_add` or `[T]::take`, are resolved to their page in the
+//! standard library docs. Other items, like `impl` blocks and syntax, are not
+//! linked. There is no way to look up what a path refers to at build time, so
+//! every item has to be listed in [`ITEMS`], except for methods and associated
+//! constants of types.
+
+use std::fmt;
+
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+enum ItemKind {
+    Module,
+    Struct,
+    Enum,
+    Union,
+    Trait,
+    Type,
+    Macro,
+    Function,
+    Constant,
+    /// Trait method that implementations have to define
+    RequiredMethod,
+    /// Trait method with a default implementation
+    ProvidedMethod,
+}
+
+use ItemKind::*;
+
+/// Known items by their path without the crate. Paths that are not written
+/// out in full are matched against the end of these, so `HashMap` finds
+/// `collections::HashMap`.
+const ITEMS: &[(&str, ItemKind)] = &[
+    // Modules
+    ("alloc", Module),
+    ("any", Module),
+    ("arch", Module),
+    ("array", Module),
+    ("ascii", Module),
+    ("backtrace", Module),
+    ("borrow", Module),
+    ("boxed", Module),
+    ("cell", Module),
+    ("char", Module),
+    ("clone", Module),
+    ("cmp", Module),
+    ("collections", Module),
+    ("convert", Module),
+    ("default", Module),
+    ("env", Module),
+    ("error", Module),
+    ("f32", Module),
+    ("f64", Module),
+    ("ffi", Module),
+    ("fmt", Module),
+    ("fs", Module),
+    ("future", Module),
+    ("hash", Module),
+    ("hint", Module),
+    ("io", Module),
+    ("iter", Module),
+    ("marker", Module),
+    ("mem", Module),
+    ("net", Module),
+    ("num", Module),
+    ("ops", Module),
+    ("option", Module),
+    ("os", Module),
+    ("panic", Module),
+    ("path", Module),
+    ("pin", Module),
+    ("process", Module),
+    ("ptr", Module),
+    ("rc", Module),
+    ("result", Module),
+    ("slice", Module),
+    ("str", Module),
+    ("string", Module),
+    ("sync", Module),
+    ("task", Module),
+    ("thread", Module),
+    ("time", Module),
+    ("vec", Module),
+    ("collections::btree_map", Module),
+    ("collections::hash_map", Module),
+    ("f32::consts", Module),
+    ("f64::consts", Module),
+    ("os::unix", Module),
+    ("os::unix::fs", Module),
+    ("os::windows", Module),
+    ("os::windows::fs", Module),
+    ("sync::atomic", Module),
+    // Structs
+    ("boxed::Box", Struct),
+    ("cell::Cell", Struct),
+    ("cell::OnceCell", Struct),
+    ("cell::RefCell", Struct),
+    ("collections::BTreeMap", Struct),
+    ("collections::BTreeSet", Struct),
+    ("collections::BinaryHeap", Struct),
+    ("collections::HashMap", Struct),
+    ("collections::HashSet", Struct),
+    ("collections::LinkedList", Struct),
+    ("collections::VecDeque", Struct),
+    ("ffi::CStr", Struct),
+    ("ffi::CString", Struct),
+    ("ffi::OsStr", Struct),
+    ("ffi::OsString", Struct),
+    ("fs::File", Struct),
+    ("fs::Metadata", Struct),
+    ("io::Cursor", Struct),
+    ("mem::ManuallyDrop", Struct),
+    ("num::NonZeroI8", Struct),
+    ("num::NonZeroI16", Struct),
+    ("num::NonZeroI32", Struct),
+    ("num::NonZeroI64", Struct),
+    ("num::NonZeroI128", Struct),
+    ("num::NonZeroIsize", Struct),
+    ("num::NonZeroU8", Struct),
+    ("num::NonZeroU16", Struct),
+    ("num::NonZeroU32", Struct),
+    ("num::NonZeroU64", Struct),
+    ("num::NonZeroU128", Struct),
+    ("num::NonZeroUsize", Struct),
+    ("num::Wrapping", Struct),
+    ("path::Path", Struct),
+    ("path::PathBuf", Struct),
+    ("ptr::NonNull", Struct),
+    ("rc::Rc", Struct),
+    ("rc::Weak", Struct),
+    ("string::String", Struct),
+    ("sync::Arc", Struct),
+    ("sync::Mutex", Struct),
+    ("sync::OnceLock", Struct),
+    ("sync::RwLock", Struct),
+    ("sync::Weak", Struct),
+    ("sync::atomic::AtomicBool", Struct),
+    ("sync::atomic::AtomicI8", Struct),
+    ("sync::atomic::AtomicI16", Struct),
+    ("sync::atomic::AtomicI32", Struct),
+    ("sync::atomic::AtomicI64", Struct),
+    ("sync::atomic::AtomicIsize", Struct),
+    ("sync::atomic::AtomicPtr", Struct),
+    ("sync::atomic::AtomicU8", Struct),
+    ("sync::atomic::AtomicU16", Struct),
+    ("sync::atomic::AtomicU32", Struct),
+    ("sync::atomic::AtomicU64", Struct),
+    ("sync::atomic::AtomicUsize", Struct),
+    ("thread::Builder", Struct),
+    ("thread::JoinHandle", Struct),
+    ("thread::Scope", Struct),
+    ("thread::ScopedJoinHandle", Struct),
+    ("time::Duration", Struct),
+    ("time::Instant", Struct),
+    ("time::SystemTime", Struct),
+    ("vec::Vec", Struct),
+    // Enums
+    ("borrow::Cow", Enum),
+    ("cmp::Ordering", Enum),
+    ("collections::btree_map::Entry", Enum),
+    ("collections::hash_map::Entry", Enum),
+    ("option::Option", Enum),
+    ("result::Result", Enum),
+    // Unions
+    ("mem::MaybeUninit", Union),
+    // Traits
+    ("convert::AsMut", Trait),
+    ("convert::AsRef", Trait),
+    ("convert::From", Trait),
+    ("convert::Into", Trait),
+    ("convert::TryFrom", Trait),
+    ("convert::TryInto", Trait),
+    ("iter::DoubleEndedIterator", Trait),
+    ("iter::ExactSizeIterator", Trait),
+    ("iter::FromIterator", Trait),
+    ("iter::IntoIterator", Trait),
+    ("iter::Iterator", Trait),
+    ("ops::AddAssign", Trait),
+    ("ops::BitAndAssign", Trait),
+    ("ops::BitOrAssign", Trait),
+    ("ops::BitXorAssign", Trait),
+    ("ops::DivAssign", Trait),
+    ("ops::MulAssign", Trait),
+    ("ops::RemAssign", Trait),
+    ("ops::ShlAssign", Trait),
+    ("ops::ShrAssign", Trait),
+    ("ops::SubAssign", Trait),
+    ("os::unix::fs::FileTypeExt", Trait),
+    ("os::windows::fs::FileTypeExt", Trait),
+    // Type aliases
+    ("ffi::c_char", Type),
+    ("ffi::c_double", Type),
+    ("ffi::c_float", Type),
+    ("ffi::c_int", Type),
+    ("ffi::c_long", Type),
+    ("ffi::c_longlong", Type),
+    ("ffi::c_schar", Type),
+    ("ffi::c_short", Type),
+    ("ffi::c_uchar", Type),
+    ("ffi::c_uint", Type),
+    ("ffi::c_ulong", Type),
+    ("ffi::c_ulonglong", Type),
+    ("ffi::c_ushort", Type),
+    // Macros
+    ("arch::asm", Macro),
+    ("arch::global_asm", Macro),
+    ("matches", Macro),
+    ("ptr::addr_of", Macro),
+    ("ptr::addr_of_mut", Macro),
+    ("todo", Macro),
+    // Functions
+    ("hint::unreachable_unchecked", Function),
+    ("io::empty", Function),
+    ("io::repeat", Function),
+    ("io::sink", Function),
+    ("ptr::write", Function),
+    ("ptr::write_unaligned", Function),
+    ("thread::scope", Function),
+    // Constants
+    ("f32::consts::TAU", Constant),
+    ("f64::consts::TAU", Constant),
+    // Trait methods, which rustdoc anchors differently depending on whether
+    // they are required or provided
+    ("iter::Iterator::is_sorted", ProvidedMethod),
+    ("iter::Iterator::is_sorted_by", ProvidedMethod),
+    ("iter::Iterator::is_sorted_by_key", ProvidedMethod),
+    ("os::windows::fs::FileTypeExt::is_symlink_dir", RequiredMethod),
+    ("os::windows::fs::FileTypeExt::is_symlink_file", RequiredMethod),
+];
+
+/// Primitive types that are written by their name. Slices and pointers are
+/// written as `[T]` and `<*const T>` / `<*mut T>`.
+const PRIMITIVES: &[&str] = &[
+    "bool", "char", "str", "f32", "f64", "i8", "i16", "i32", "i64", "i128", "isize", "u8", "u16",
+    "u32", "u64", "u128", "usize",
+];
+
+/// An item written as a path, with generic arguments removed.
+struct ItemPath<'a> {
+    /// Primitive type the path starts with, if any
+    primitive: Option<&'a str>,
+    segments: Vec<&'a str>,
+}
+
+/// Finds the documentation page of `item`, relative to
+/// https://doc.rust-lang.org/{channel}/. Returns `None` for items that are
+/// not written as paths.
+pub fn doc_path(item: &str) -> Result<Option<String>, UnresolvedItem> {
+    let Some(path) = parse(item) else { return Ok(None) };
+    match resolve(&path) {
+        Ok(doc_path) => Ok(Some(doc_path)),
+        Err(reason) => Err(UnresolvedItem { item: item.to_owned(), reason }),
+    }
+}
+
+fn parse(item: &str) -> Option<ItemPath<'_>> {
+    let (primitive, rest) = if let Some(rest) = item.strip_prefix("[T]::") {
+        (Some("slice"), rest)
+    } else if let Some(ty) = item.strip_prefix("<*const ").or_else(|| item.strip_prefix("<*mut ")) {
+        (Some("pointer"), ty.split_once(">::")?.1)
+    } else {
+        (None, item)
+    };
+
+    let mut segments = Vec::new();
+    for segment in rest.split("::") {
+        // Generic arguments don't change the page, as in `Wrapping<T>::reverse_bits`
+        let name = match segment.split_once('<') {
+            Some((name, args)) if args.ends_with('>') => name,
+            Some(_) => return None,
+            None => segment,
+        };
+        let is_ident = name.starts_with(|c: char| c.is_ascii_alphabetic() || c == '_')
+            && name.chars().all(|c| c.is_ascii_alphanumeric() || c == '_');
+        if !is_ident {
+            return None;
+        }
+        segments.push(name);
+    }
+
+    let primitive = primitive.or_else(|| PRIMITIVES.iter().copied().find(|&p| p == segments[0]));
+    let is_path = segments.len() > 1 || primitive.is_some();
+    is_path.then_some(ItemPath { primitive, segments })
+}
+
+/// Resolves the path to a documentation page, or returns why that isn't
+/// possible.
+fn resolve(path: &ItemPath<'_>) -> Result<String, String> {
+    let segments = &path.segments[..];
+    let (page, members) = match path.primitive {
+        Some(p @ ("slice" | "pointer")) => (format!("std/primitive.{p}.html"), segments),
+        Some(p) => (format!("std/primitive.{p}.html"), &segments[1..]),
+        None => {
+            let (krate, segments) = match segments[0] {
+                "std" | "core" | "alloc" => (segments[0], &segments[1..]),
+                _ => ("std", segments),
+            };
+            // Paths that start with the crate are written out in full
+            let exact = segments.len() < path.segments.len();
+
+            let mut found = None;
+            for n in (1..=segments.len()).rev() {
+                if let Some((item_path, kind)) = find_item(&segments[..n], exact)? {
+                    // Members of a module are items of their own
+                    if kind != Module || n == segments.len() {
+                        found = Some((item_path, kind, n));
+                        break;
+                    }
+                }
+            }
+
+            match found {
+                Some((item_path, Trait, n)) if n < segments.len() => {
+                    let method = segments[n..].join("::");
+                    return Err(format!(
+                        "unknown method `{method}` of `{item_path}`, add it to `ITEMS` in \
+                         src/rustdoc.rs"
+                    ));
+                }
+                Some((item_path, kind, n)) => (item_page(krate, item_path, kind), &segments[n..]),
+                None => {
+                    return Err(format!(
+                        "unknown item `{}`, add it to `ITEMS` in src/rustdoc.rs",
+                        segments.join("::")
+                    ));
+                }
+            }
+        }
+    };
+
+    match members {
+        [] => Ok(page),
+        [member] if is_constant(member) => Ok(format!("{page}#associatedconstant.{member}")),
+        [member] if member.starts_with(|c: char| c.is_ascii_lowercase()) => {
+            Ok(format!("{page}#method.{member}"))
+        }
+        [member] => Err(format!("`{member}` is neither a method nor a constant")),
+        [_, member, ..] => Err(format!("`{member}` is nested too deeply")),
+    }
+}
+
+/// Looks up the item in [`ITEMS`], by its full path or the end of it.
+fn find_item(segments: &[&str], exact: bool) -> Result<Option<(&'static str, ItemKind)>, String> {
+    let path = segments.join("::");
+    let suffix = format!("::{path}");
+    let mut matches = ITEMS
+        .iter()
+        .filter(|(item_path, _)| *item_path == path || !exact && item_path.ends_with(&suffix));
+
+    match (matches.next(), matches.next()) {
+        (None, _) => Ok(None),
+        (Some(&found), None) => Ok(Some(found)),
+        (Some((a, _)), Some((b, _))) => {
+            Err(format!("`{path}` could be `{a}` or `{b}`, use a longer path"))
+        }
+    }
+}
+
+fn item_page(krate: &str, item_path: &str, kind: ItemKind) -> String {
+    let kind = match kind {
+        Module => return format!("{krate}/{}/index.html", item_path.replace("::", "/")),
+        Struct => "struct",
+        Enum => "enum",
+        Union => "union",
+        Trait => "trait",
+        Type => "type",
+        Macro => "macro",
+        Function => "fn",
+        Constant => "constant",
+        RequiredMethod | ProvidedMethod => {
+            let (trait_path, name) = item_path.rsplit_once("::").unwrap();
+            let anchor = if kind == RequiredMethod { "tymethod" } else { "method" };
+            return format!("{}#{anchor}.{name}", item_page(krate, trait_path, Trait));
+        }
+    };
+    match item_path.rsplit_once("::") {
+        Some((module, name)) => format!("{krate}/{}/{kind}.{name}.html", module.replace("::", "/")),
+        None => format!("{krate}/{kind}.{item_path}.html"),
+    }
+}
+
+fn is_constant(name: &str) -> bool {
+    name.starts_with(|c: char| c.is_ascii_uppercase())
+        && name.chars().all(|c| c.is_ascii_uppercase() || c.is_ascii_digit() || c == '_')
+}
+
+#[derive(Debug)]
+pub struct UnresolvedItem {
+    item: String,
+    reason: String,
+}
+
+impl fmt::Display for UnresolvedItem {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "can't link item `{}`: {}", self.item, self.reason)
+    }
+}
+
+impl std::error::Error for UnresolvedItem {}
+
+#[cfg(test)]
+mod tests {
+    use super::doc_path;
+
+    fn path(item: &str) -> String {
+        doc_path(item).unwrap().unwrap()
+    }
+
+    #[test]
+    fn page_forms() {
+        assert_eq!(path("std::thread::scope"), "std/thread/fn.scope.html");
+        assert_eq!(path("std::thread::Scope"), "std/thread/struct.Scope.html");
+        assert_eq!(path("ops::AddAssign"), "std/ops/trait.AddAssign.html");
+        assert_eq!(path("core::arch::asm"), "core/arch/macro.asm.html");
+        assert_eq!(path("core::ffi::c_int"), "core/ffi/type.c_int.html");
+        assert_eq!(path("std::sync::atomic"), "std/sync/atomic/index.html");
+        assert_eq!(path("std::f32::consts::TAU"), "std/f32/consts/constant.TAU.html");
+        assert_eq!(path("u128"), "std/primitive.u128.html");
+    }
+
+    #[test]
+    fn members() {
+        assert_eq!(path("i8::unsigned_abs"), "std/primitive.i8.html#method.unsigned_abs");
+        assert_eq!(path("f32::LOG2_10"), "std/primitive.f32.html#associatedconstant.LOG2_10");
+        assert_eq!(path("[T]::take"), "std/primitive.slice.html#method.take");
+        assert_eq!(path("<*const [T]>::len"), "std/primitive.pointer.html#method.len");
+        assert_eq!(
+            path("Wrapping<T>::reverse_bits"),
+            "std/num/struct.Wrapping.html#method.reverse_bits"
+        );
+        assert_eq!(
+            path("HashMap::get_key_value"),
+            "std/collections/struct.HashMap.html#method.get_key_value"
+        );
+        assert_eq!(
+            path("collections::btree_map::Entry::or_insert_with_key"),
+            "std/collections/btree_map/enum.Entry.html#method.or_insert_with_key"
+        );
+        assert_eq!(
+            path("std::mem::MaybeUninit::as_ptr"),
+            "std/mem/union.MaybeUninit.html#method.as_ptr"
+        );
+        assert_eq!(path("Iterator::is_sorted"), "std/iter/trait.Iterator.html#method.is_sorted");
+        assert_eq!(
+            path("std::os::windows::fs::FileTypeExt::is_symlink_dir"),
+            "std/os/windows/fs/trait.FileTypeExt.html#tymethod.is_symlink_dir"
+        );
+    }
+
+    #[test]
+    fn not_paths() {
+        for item in ["impl From<bool> for f32", "#[allow(tool::lint)]", "async fn", "yield", "..=X"]
+        {
+            assert_eq!(doc_path(item).unwrap(), None, "{item}");
+        }
+    }
+
+    #[test]
+    fn unresolved() {
+        for item in [
+            "Entry::or_default",
+            "Foo::bar",
+            "foo::bar",
+            "std::HashMap",
+            "i8::a::b",
+            "std::os::fd",
+            "std::io::prelude",
+            "std::thread::spawn",
+            "Iterator::next",
+        ] {
+            assert!(doc_path(item).is_err(), "{item}");
+        }
+    }
+}
@@ -0,0 +1,83 @@
+//! Scroll positions of previously visited pages, for restoring them when
+//! navigating back or forward.
+
+use std::{cell::RefCell, collections::HashMap, mem};
+
+use gloo_utils::window;
+use js_sys::Reflect;
+
+thread_local! {
+    static STATE: RefCell<State> = RefCell::default();
+}
+
+#[derive(Default)]
+struct State {
+    /// Last scroll position of every visited history entry, by entry id
+    positions: HashMap<Option<u32>, f64>,
+    /// Scroll position to restore once the current page is rendered, if it
+    /// was reached through back / forward navigation
+    pending_restore: Option<f64>,
+    /// Whether the pending location change replaces the current history
+    /// entry rather than creating a new one
+    replacing: bool,
+}
+
+/// Id of the current history entry. The router stores a unique one in the
+/// state of every entry it creates; the entry the app was loaded with has
+/// none.
+fn current_entry() -> Option<u32> {
+    let state = window().history().ok()?.state().ok()?;
+    let id = Reflect::get(&state, &"id".into()).ok()?.as_f64()?;
+    Some(id as u32)
+}
+
+/// Records the scroll position of the current page.
+pub fn save_position() {
+    STATE.with(|state| {
+        let mut state = state.borrow_mut();
+        // While restoring, scroll events are caused by the page being
+        // re-rendered and don't reflect the saved position
+        if state.pending_restore.is_none() {
+            state.positions.insert(current_entry(), window().scroll_y().unwrap());
+        }
+    });
+}
+
+/// Starts restoring the saved scroll position of the current page, after
+/// navigating back or forward to it.
+pub fn start_restore() {
+    STATE.with(|state| {
+        let mut state = state.borrow_mut();
+        state.pending_restore = Some(state.positions.get(&current_entry()).copied().unwrap_or(0.0));
+    });
+}
+
+/// Whether the current page is being restored through back / forward
+/// navigation, in which case components should render the way they were last
+/// rendered on this page.
+pub fn is_restoring() -> bool {
+    STATE.with(|state| state.borrow().pending_restore.is_some())
+}
+
+/// Marks the next location change as replacing the current history entry,
+/// which keeps the scroll position. Has to be called right before replacing
+/// it through the router.
+pub fn replace_entry() {
+    STATE.with(|state| state.borrow_mut().replacing = true);
+}
+
+/// Scrolls to the restored position if back / forward navigation happened,
+/// and to the top of the page if a new history entry was pushed.
+pub fn finish_navigation() {
+    let (position, replacing) = STATE.with(|state| {
+        let mut state = state.borrow_mut();
+        (state.pending_restore.take(), mem::take(&mut state.replacing))
+    });
+
+    match position {
+        Some(position) => window().scroll_to_with_x_and_y(0.0, position),
+        // The replacing entry has a new id, so the position is saved again
+        None if replacing => save_position(),
+        None => window().scroll_to_with_x_and_y(0.0, 0.0),
+    }
+}
@@ -1,7 +1,100 @@
-use crate::data::{
-    FeatureData, FEATURES, FEATURE_BIGRAM_INDEX, FEATURE_MONOGRAM_INDEX, FEATURE_TRIGRAM_INDEX,
+use std::{collections::HashSet, iter};
+
+use crate::{
+    data::{
+        FeatureData, VersionData, FEATURES, FEATURE_BIGRAM_INDEX, FEATURE_FLAG_INDEX,
+        FEATURE_ISSUE_INDEX, FEATURE_MONOGRAM_INDEX, FEATURE_PR_INDEX, FEATURE_RFC_INDEX,
+        FEATURE_TRIGRAM_INDEX, VERSIONS,
+    },
+    markup,
 };
 
+/// Minimum score for a feature to be considered a search result
+const MATCH_THRESHOLD: f64 = 0.25;
+/// Minimum score for a feature to be listed as a weak match
+const WEAK_MATCH_THRESHOLD: f64 = 0.15;
+const MAX_WEAK_MATCHES: usize = 10;
+const MAX_SUGGESTIONS: usize = 5;
+
+/// A search query that identifies features exactly rather than by similarity
+#[derive(Debug, PartialEq)]
+enum ExactQuery<'a> {
+    /// `feature(foo)`, optionally wrapped in `#![...]`
+    Flags(Vec<&'a str>),
+    /// `rfc 1234`
+    Rfc(u64),
+    /// `#12345`, a rust-lang/rust PR or tracking issue
+    IssueOrPr(u64),
+}
+
+fn parse_exact_query(query: &str) -> Option<ExactQuery<'_>> {
+    let query = query.trim();
+
+    let attr_contents = query
+        .strip_prefix("#![")
+        .or_else(|| query.strip_prefix("#["))
+        .map(|q| q.strip_suffix(']').unwrap_or(q))
+        .unwrap_or(query);
+    if let Some(flags) = attr_contents.strip_prefix("feature(") {
+        let flags = flags.strip_suffix(')').unwrap_or(flags);
+        let flags = flags.split(',').map(str::trim).filter(|f| !f.is_empty()).collect();
+        return Some(ExactQuery::Flags(flags));
+    }
+
+    if let Some(number) = query.strip_prefix('#') {
+        return number.parse().ok().map(ExactQuery::IssueOrPr);
+    }
+
+    if query.get(..3).is_some_and(|prefix| prefix.eq_ignore_ascii_case("rfc")) {
+        let number = query[3..].trim_start().trim_start_matches('#');
+        return number.parse().ok().map(ExactQuery::Rfc);
+    }
+
+    None
+}
+
+/// Looks up features by feature flag, RFC, PR or tracking issue number if the
+/// query has one of the shapes `feature(foo)`, `rfc 1234` or `#12345`.
+///
+/// Returns `None` for all other queries.
+pub fn exact_matches(query: &str) -> Option<Vec<FeatureData>> {
+    let mut indices: Vec<u16> = match parse_exact_query(query)? {
+        ExactQuery::Flags(flags) => flags
+            .into_iter()
+            .filter_map(|flag| FEATURE_FLAG_INDEX.get(flag))
+            .flat_map(|idx| idx.iter().copied())
+            .collect(),
+        ExactQuery::Rfc(id) => FEATURE_RFC_INDEX.get(&id).map_or(Vec::new(), |idx| idx.to_vec()),
+        ExactQuery::IssueOrPr(id) => [FEATURE_PR_INDEX.get(&id), FEATURE_ISSUE_INDEX.get(&id)]
+            .into_iter()
+            .flatten()
+            .flat_map(|idx| idx.iter().copied())
+            .collect(),
+    };
+
+    indices.sort_unstable();
+    indices.dedup();
+    Some(indices.into_iter().map(|idx| FEATURES[idx as usize]).collect())
+}
+
+/// Suggestions for an exact query that matched nothing, i.e. feature flags
+/// that are close to the ones in a `feature(...)` query.
+pub fn exact_suggestions(query: &str) -> Vec<&'static str> {
+    let Some(ExactQuery::Flags(flags)) = parse_exact_query(query) else {
+        return Vec::new();
+    };
+
+    // Keeps the suggestions for each flag in order, the closest first
+    let mut seen = HashSet::new();
+    let mut res: Vec<_> = flags
+        .iter()
+        .flat_map(|&flag| suggestions(&[flag.to_owned()]))
+        .filter(|&suggestion| seen.insert(suggestion))
+        .collect();
+    res.truncate(MAX_SUGGESTIONS);
+    res
+}
+
 /// Search query contains '`' or a non-ascii character
 #[derive(Debug)]
 pub struct InvalidSearchQuery;
@@ -21,7 +114,14 @@ pub fn extract_search_terms(query: &str) -> Result<Vec<String>, InvalidSearchQue
         .collect()
 }
 
-pub fn run_search(search_terms: &[String], search_scores: &mut [(u16, f64)]) -> Vec<FeatureData> {
+#[derive(Debug, Default)]
+pub struct SearchResults {
+    pub matches: Vec<FeatureData>,
+    /// Features that scored just below the cutoff for regular matches
+    pub weak_matches: Vec<FeatureData>,
+}
+
+pub fn run_search(search_terms: &[String], search_scores: &mut [(u16, f64)]) -> SearchResults {
     for (i, (idx, score)) in search_scores.iter_mut().enumerate() {
         *idx = i as u16;
         *score = 0.0;
@@ -83,11 +183,97 @@ pub fn run_search(search_terms: &[String], search_scores: &mut [(u16, f64)]) ->
             FEATURES[*idx_a as usize].title.len().cmp(&FEATURES[*idx_b as usize].title.len())
         })
     });
-    search_scores
+
+    let matches = search_scores
         .iter()
-        .filter(|(_, score)| *score >= 0.25)
+        .take_while(|(_, score)| *score >= MATCH_THRESHOLD)
         .map(|(idx, _)| FEATURES[*idx as usize])
-        .collect()
+        .collect();
+    let weak_matches = search_scores
+        .iter()
+        .skip_while(|(_, score)| *score >= MATCH_THRESHOLD)
+        .take_while(|(_, score)| *score >= WEAK_MATCH_THRESHOLD)
+        .take(MAX_WEAK_MATCHES)
+        .map(|(idx, _)| FEATURES[*idx as usize])
+        .collect();
+
+    SearchResults { matches, weak_matches }
+}
+
+/// Returns the feature titles, flags and aliases closest to the search terms,
+/// for queries that don't have any regular matches.
+pub fn suggestions(search_terms: &[String]) -> Vec<&'static str> {
+    let query = search_terms.join(" ").to_lowercase();
+    let max_distance = (query.chars().count() / 3).max(1);
+
+    let mut candidates: Vec<_> = FEATURES
+        .iter()
+        .flat_map(|f| iter::once(f.title).chain(f.flag).chain(f.aliases.iter().copied()))
+        .filter_map(|candidate| {
+            let distance = edit_distance(&query, &markup::to_plain(candidate).to_lowercase());
+            (distance <= max_distance).then_some((distance, candidate))
+        })
+        .collect();
+
+    candidates.sort_unstable();
+    candidates.dedup();
+    candidates.into_iter().take(MAX_SUGGESTIONS).map(|(_, candidate)| candidate).collect()
+}
+
+/// Returns the features that best match the given slug or path segment, for
+/// suggesting them on "not found" pages.
+pub fn closest_features(slug: &str, max: usize) -> Vec<FeatureData> {
+    let Ok(search_terms) = extract_search_terms(&slug.replace(['_', '-', '/'], " ")) else {
+        return Vec::new();
+    };
+    if search_terms.is_empty() {
+        return Vec::new();
+    }
+
+    let mut search_scores = vec![(0, 0.0); FEATURES.len()];
+    let SearchResults { matches, weak_matches } = run_search(&search_terms, &mut search_scores);
+    matches.into_iter().chain(weak_matches).take(max).collect()
+}
+
+/// Returns the versions whose number is closest to the given one, for
+/// suggesting them on "not found" pages.
+pub fn closest_versions(number: &str, max: usize) -> Vec<&'static VersionData> {
+    let mut candidates: Vec<_> = VERSIONS
+        .iter()
+        .map(|v| (edit_distance(number, &v.number.to_string()), v))
+        .filter(|(distance, _)| *distance <= 2)
+        .collect();
+
+    candidates.sort_by_key(|(distance, _)| *distance);
+    candidates.into_iter().take(max).map(|(_, v)| v).collect()
+}
+
+/// Returns the first search term that looks like a path into the standard
+/// library, like `std::ffi::c_int`.
+pub fn std_path(search_terms: &[String]) -> Option<&str> {
+    search_terms.iter().map(String::as_str).find(|term| {
+        matches!(term.split_once("::"), Some(("std" | "core" | "alloc", rest)) if !rest.is_empty())
+            && term.bytes().all(|b| b.is_ascii_alphanumeric() || b == b'_' || b == b':')
+    })
+}
+
+/// Levenshtein distance between two strings
+fn edit_distance(a: &str, b: &str) -> usize {
+    let b: Vec<char> = b.chars().collect();
+    let mut row: Vec<usize> = (0..=b.len()).collect();
+
+    for (i, ca) in a.chars().enumerate() {
+        let mut diagonal = row[0];
+        row[0] = i + 1;
+
+        for (j, &cb) in b.iter().enumerate() {
+            let substitution = diagonal + usize::from(ca != cb);
+            diagonal = row[j + 1];
+            row[j + 1] = substitution.min(row[j] + 1).min(row[j + 1] + 1);
+        }
+    }
+
+    row[b.len()]
 }
 
 #[cfg(test)]
@@ -121,4 +307,89 @@ mod tests {
         assert!(extract_search_terms(" `a`").is_err());
         assert!(extract_search_terms(" x `").is_err());
     }
+
+    #[test]
+    fn parse_exact_queries() {
+        assert_eq!(parse_exact_query("#12345"), Some(ExactQuery::IssueOrPr(12345)));
+        assert_eq!(parse_exact_query(" rfc 2497 "), Some(ExactQuery::Rfc(2497)));
+        assert_eq!(parse_exact_query("RFC #2497"), Some(ExactQuery::Rfc(2497)));
+        assert_eq!(parse_exact_query("rfc2497"), Some(ExactQuery::Rfc(2497)));
+        assert_eq!(
+            parse_exact_query("feature(let_chains)"),
+            Some(ExactQuery::Flags(vec!["let_chains"]))
+        );
+        assert_eq!(
+            parse_exact_query("#![feature(let_chains, never_type)]"),
+            Some(ExactQuery::Flags(vec!["let_chains", "never_type"]))
+        );
+
+        assert_eq!(parse_exact_query("let_chains"), None);
+        assert_eq!(parse_exact_query("rfcs"), None);
+        assert_eq!(parse_exact_query("#abc"), None);
+        assert_eq!(parse_exact_query("rfc"), None);
+        assert_eq!(parse_exact_query("äh"), None);
+    }
+
+    #[test]
+    fn exact_lookup() {
+        let slugs = |query| {
+            exact_matches(query).map(|matches| matches.iter().map(|f| f.slug).collect::<Vec<_>>())
+        };
+
+        assert_eq!(slugs("feature(let_else)"), Some(vec!["let_else"]));
+        assert_eq!(slugs("rfc 3137"), Some(vec!["let_else"]));
+        assert_eq!(slugs("#87335"), Some(vec!["let_else"]));
+        assert_eq!(slugs("#93628"), Some(vec!["let_else"]));
+        assert_eq!(slugs("feature(does_not_exist)"), Some(vec![]));
+        assert_eq!(slugs("let else"), None);
+    }
+
+    #[test]
+    fn exact_lookup_suggestions() {
+        assert!(exact_suggestions("#![feature(let_elze)]").contains(&"let_else"));
+        assert!(exact_suggestions("#99999999").is_empty());
+
+        let suggestions = exact_suggestions("#![feature(let_elze, never_typ, let_els)]");
+        assert_eq!(suggestions.iter().filter(|&&s| s == "let_else").count(), 1);
+    }
+
+    #[test]
+    fn edit_distances() {
+        assert_eq!(edit_distance("", ""), 0);
+        assert_eq!(edit_distance("let_else", "let_else"), 0);
+        assert_eq!(edit_distance("let_els", "let_else"), 1);
+        assert_eq!(edit_distance("lte_else", "let_else"), 2);
+        assert_eq!(edit_distance("kitten", "sitting"), 3);
+        assert_eq!(edit_distance("", "abc"), 3);
+    }
+
+    #[test]
+    fn suggest_misspelled_flag() {
+        let terms = extract_search_terms("let_elze").unwrap();
+        assert!(suggestions(&terms).contains(&"let_else"));
+    }
+
+    #[test]
+    fn closest_to_missing_slug() {
+        let slugs: Vec<_> = closest_features("let_els", 5).iter().map(|f| f.slug).collect();
+        assert_eq!(slugs.first(), Some(&"let_else"));
+        assert!(closest_features("", 5).is_empty());
+    }
+
+    #[test]
+    fn closest_to_missing_version() {
+        let numbers: Vec<_> =
+            closest_versions("1.650", 3).iter().map(|v| v.number.to_string()).collect();
+        assert_eq!(numbers.first().map(String::as_str), Some("1.65"));
+        assert!(closest_versions("foobar", 3).is_empty());
+    }
+
+    #[test]
+    fn detect_std_path() {
+        let terms = extract_search_terms("foo std::ffi::c_int").unwrap();
+        assert_eq!(std_path(&terms), Some("std::ffi::c_int"));
+        assert_eq!(std_path(&extract_search_terms("core::").unwrap()), None);
+        assert_eq!(std_path(&extract_search_terms("serde::Serialize").unwrap()), None);
+        assert_eq!(std_path(&extract_search_terms("let_else").unwrap()), None);
+    }
 }
@@ -0,0 +1,194 @@
+//! Working out the user's Rust version from a `rust-toolchain(.toml)` or
+//! `Cargo.toml` file.
+
+use crate::{
+    data::{find_version, latest_stable, Channel},
+    version::RustVersion,
+    VERSIONS,
+};
+
+/// The toolchain a project asks for.
+#[derive(Clone, Debug, PartialEq)]
+pub enum Toolchain {
+    /// A specific version, from `rust-version` or a toolchain `channel` like
+    /// `1.70.0`
+    Version(RustVersion),
+    /// A release channel, optionally for a specific date (`nightly-2024-05-01`)
+    Channel { channel: Channel, date: Option<String> },
+}
+
+impl Toolchain {
+    /// The version of Rust this toolchain corresponds to, if it is one this
+    /// site knows about.
+    pub fn resolve(&self) -> Option<RustVersion> {
+        let version = match self {
+            Toolchain::Version(version) => version.without_patch(),
+            Toolchain::Channel { channel, date } => {
+                let stable = match date {
+                    Some(date) => {
+                        let date = parse_date(date)?;
+                        VERSIONS
+                            .iter()
+                            .filter(|v| !v.release_date_estimated)
+                            .filter(|v| {
+                                v.release_date
+                                    .and_then(parse_date)
+                                    .is_some_and(|release| release <= date)
+                            })
+                            .map(|v| v.number)
+                            .max()?
+                    }
+                    None => latest_stable()?,
+                };
+
+                // Beta is one release ahead of stable, nightly two
+                let releases_ahead = match channel {
+                    Channel::Stable => 0,
+                    Channel::Beta => 1,
+                    Channel::Nightly => 2,
+                };
+                RustVersion::new(stable.major, stable.minor + releases_ahead, None)
+            }
+        };
+
+        find_version(version).map(|v| v.number)
+    }
+}
+
+/// Finds the toolchain in the contents of a `rust-toolchain.toml`,
+/// `rust-toolchain` or `Cargo.toml` file.
+///
+/// This only understands the common ways of writing these files rather than
+/// all of TOML, which is fine since it's just a convenience.
+pub fn parse_toolchain_file(contents: &str) -> Option<Toolchain> {
+    let mut section = "";
+    let mut lines =
+        contents.lines().map(str::trim).filter(|l| !l.is_empty() && !l.starts_with('#'));
+
+    // The legacy `rust-toolchain` file only contains the channel
+    if let [line] = lines.clone().collect::<Vec<_>>()[..] {
+        if !line.contains(['=', '[']) {
+            return parse_channel(line);
+        }
+    }
+
+    lines.find_map(|line| {
+        if let Some(header) = line.strip_prefix('[') {
+            section = header.split(']').next().unwrap_or("").trim();
+            return None;
+        }
+
+        let (key, value) = line.split_once('=')?;
+        let value = string_value(value)?;
+        match (section, key.trim()) {
+            ("toolchain", "channel") => parse_channel(value),
+            ("package" | "workspace.package", "rust-version") => {
+                value.parse().ok().map(Toolchain::Version)
+            }
+            _ => None,
+        }
+    })
+}
+
+/// Parses a toolchain channel of the form `<channel>[-<date>][-<host>]`.
+fn parse_channel(s: &str) -> Option<Toolchain> {
+    let (name, rest) = s.split_once('-').unwrap_or((s, ""));
+    let channel = match name {
+        "stable" => Channel::Stable,
+        "beta" => Channel::Beta,
+        "nightly" => Channel::Nightly,
+        _ => return name.parse().ok().map(Toolchain::Version),
+    };
+
+    let date = rest.get(..10).filter(|d| {
+        d.char_indices().all(|(i, c)| if i == 4 || i == 7 { c == '-' } else { c.is_ascii_digit() })
+    });
+    Some(Toolchain::Channel { channel, date: date.map(ToOwned::to_owned) })
+}
+
+/// Parses a date in format `yyyy-mm-dd` into a tuple that compares the way
+/// the dates do.
+fn parse_date(date: &str) -> Option<(u16, u8, u8)> {
+    let mut parts = date.splitn(3, '-');
+    let year = parts.next()?.parse().ok()?;
+    let month = parts.next()?.parse().ok()?;
+    let day = parts.next()?.parse().ok()?;
+    Some((year, month, day))
+}
+
+/// Parses a quoted TOML string, ignoring a trailing comment.
+fn string_value(value: &str) -> Option<&str> {
+    let value = value.trim();
+    let quote = value.chars().next().filter(|&c| c == '"' || c == '\'')?;
+    let rest = &value[1..];
+    rest.find(quote).map(|end| &rest[..end])
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{parse_toolchain_file, Toolchain};
+    use crate::{data::Channel, version::RustVersion};
+
+    #[test]
+    fn rust_toolchain_toml() {
+        let contents = "[toolchain]\nchannel = \"1.70.0\" # MSRV\ncomponents = [\"rustfmt\"]\n";
+        assert_eq!(
+            parse_toolchain_file(contents),
+            Some(Toolchain::Version(RustVersion::new(1, 70, Some(0))))
+        );
+
+        let contents = "[toolchain]\nchannel = 'nightly-2024-05-01'\n";
+        assert_eq!(
+            parse_toolchain_file(contents),
+            Some(Toolchain::Channel {
+                channel: Channel::Nightly,
+                date: Some("2024-05-01".to_owned())
+            })
+        );
+    }
+
+    #[test]
+    fn legacy_rust_toolchain() {
+        assert_eq!(
+            parse_toolchain_file("stable-x86_64-unknown-linux-gnu\n"),
+            Some(Toolchain::Channel { channel: Channel::Stable, date: None })
+        );
+        assert_eq!(
+            parse_toolchain_file("1.65\n"),
+            Some(Toolchain::Version(RustVersion::new(1, 65, None)))
+        );
+    }
+
+    #[test]
+    fn cargo_toml() {
+        let contents = "[package]\nname = \"foo\"\nversion = \"0.1.0\"\nrust-version = \"1.56\"\n";
+        assert_eq!(
+            parse_toolchain_file(contents),
+            Some(Toolchain::Version(RustVersion::new(1, 56, None)))
+        );
+
+        let contents = "[workspace.package]\nrust-version = \"1.74.1\"\n";
+        assert_eq!(
+            parse_toolchain_file(contents),
+            Some(Toolchain::Version(RustVersion::new(1, 74, Some(1))))
+        );
+
+        // Only the package's own rust-version counts
+        let contents = "[package]\nname = \"foo\"\n\n[dependencies]\nrust-version = \"1.0\"\n";
+        assert_eq!(parse_toolchain_file(contents), None);
+    }
+
+    #[test]
+    fn resolve() {
+        let nightly =
+            Toolchain::Channel { channel: Channel::Nightly, date: Some("2021-06-01".to_owned()) };
+        assert_eq!(nightly.resolve(), Some(RustVersion::new(1, 54, None)));
+        assert_eq!(
+            Toolchain::Version(RustVersion::new(1, 56, Some(1))).resolve(),
+            Some(RustVersion::new(1, 56, None))
+        );
+
+        // Versions this site doesn't know about can't be resolved
+        assert_eq!(Toolchain::Version(RustVersion::new(1, 999, None)).resolve(), None);
+    }
+}
@@ -1,51 +1,81 @@
-use std::fmt::Display;
+use std::collections::HashMap;
 
-use yew::{
-    html,
-    virtual_dom::{VList, VNode, VTag, VText},
-    Classes, Html,
-};
+use gloo_utils::{document, window};
+use wasm_bindgen::JsCast;
+use web_sys::{Element, Event, HtmlElement, KeyboardEvent};
+use yew::{html, Callback, Classes, Html};
+use yew_router::history::Location;
 
-use crate::{icons::fa_home, AppRoute, RouterLink};
+use crate::{
+    icons::{fa_home, fa_star},
+    markup::{self, Inline},
+    version::RustVersion,
+    AppRoute, Link, RouterLink, VersionData,
+};
 
 pub enum Void {}
 
-pub fn view_text(mut text: &str) -> Html {
-    fn list_to_node(list: VList) -> VNode {
-        if list.len() == 1 {
-            list[0].clone()
-        } else {
-            VNode::VList(list)
-        }
-    }
+/// Document title used by `templates/skel.html`
+const DEFAULT_TITLE: &str = "caniuse.rs | Rust feature search";
 
-    let mut res = VList::new();
+/// Returns the version saved as "my Rust version", if any.
+pub fn stored_toolchain() -> Option<RustVersion> {
+    let storage = window().local_storage().ok()??;
+    storage.get_item("toolchain").ok()??.parse().ok()
+}
 
-    while let Some(backtick_pos) = text.find('`') {
-        if backtick_pos != 0 {
-            res.add_child(text[..backtick_pos].into());
+/// Saves the version to use as "my Rust version", or clears it for `None`.
+pub fn store_toolchain(toolchain: Option<RustVersion>) {
+    if let Ok(Some(st)) = window().local_storage() {
+        match toolchain {
+            Some(version) => st.set_item("toolchain", &version.to_string()).unwrap(),
+            None => st.remove_item("toolchain").unwrap(),
         }
-        text = &text[backtick_pos + 1..];
+    }
+}
 
-        let next_backtick_pos = match text.find('`') {
-            Some(pos) => pos,
-            None => {
-                // This should never happen, backticks should be balanced
-                break;
-            }
-        };
+/// Sets the document title to `{title} | caniuse.rs`, or to the default title
+/// for `None`. Markup is removed.
+pub fn set_title(title: Option<&str>) {
+    match title {
+        Some(title) => document().set_title(&format!("{} | caniuse.rs", markup::to_plain(title))),
+        None => document().set_title(DEFAULT_TITLE),
+    }
+}
 
-        let mut code = VTag::new("code");
-        code.add_child(text[..next_backtick_pos].into());
-        text = &text[next_backtick_pos + 1..];
+/// Renders the Markdown subset of titles and descriptions, see
+/// [`markup`](crate::markup).
+pub fn view_text(text: &str) -> Html {
+    fn view_inlines(inlines: &[Inline<'_>]) -> Html {
+        inlines.iter().map(view_inline).collect()
+    }
 
-        res.add_child(VNode::VTag(Box::new(code)));
+    fn view_inline(inline: &Inline<'_>) -> Html {
+        match inline {
+            Inline::Text(text) => html! { {*text} },
+            Inline::Code(code) => html! { <code>{*code}</code> },
+            Inline::Emphasis(content) => html! { <em>{view_inlines(content)}</em> },
+            Inline::Strong(content) => html! { <strong>{view_inlines(content)}</strong> },
+            Inline::Link { content, url } => {
+                html! { <a href={url.to_string()}>{view_inlines(content)}</a> }
+            }
+        }
     }
 
-    // Use the rest of the text verbatim
-    res.add_child(VNode::VText(VText::new(text.to_owned())));
+    view_inlines(&markup::parse(text))
+}
 
-    list_to_node(res)
+/// Release date of `v`, marked as such if it is only an estimate.
+pub fn view_release_date(v: &VersionData) -> Html {
+    match v.release_date {
+        Some(date) if v.release_date_estimated => html! {
+            <time datetime={date} title="Estimated from the six-week release cycle">
+                {"expected ~"}{date}
+            </time>
+        },
+        Some(date) => html! { <time datetime={date}>{date}</time> },
+        None => html! {},
+    }
 }
 
 pub fn home_button() -> Html {
@@ -55,13 +85,70 @@ pub fn home_button() -> Html {
     }
 }
 
-pub fn link<T: Display>(text: &str, link_base: &str, rest: T) -> Html {
-    html! { <li><a href={format!("{link_base}{rest}")}>{text}</a></li> }
+/// Button for adding the feature to the watchlist or removing it.
+pub fn watch_button(
+    slug: &'static str,
+    watched: bool,
+    onwatchtoggle: &Callback<&'static str>,
+) -> Html {
+    let (classes, title) = if watched {
+        ("watch active", "Remove from watchlist")
+    } else {
+        ("watch", "Add to watchlist")
+    };
+    let onclick = onwatchtoggle.reform(move |_| slug);
+
+    html! {
+        <button type="button" class={classes} title={title} aria-label={title}
+            aria-pressed={watched.to_string()} onclick={onclick}>
+            {fa_star()}
+        </button>
+    }
+}
+
+/// Link to the search results for `query`.
+pub fn search_link(query: &str, contents: Html) -> Html {
+    type QueryLink = yew_router::components::Link<AppRoute, [(&'static str, String); 1]>;
+
+    html! {
+        <QueryLink to={AppRoute::Index} query={Some([("q", query.to_owned())])}>
+            {contents}
+        </QueryLink>
+    }
 }
 
-pub fn maybe_link<T: Display>(text: &str, link_base: &str, opt_rest: Option<T>) -> Html {
-    match opt_rest {
-        Some(rest) => link(text, link_base, rest),
-        None => html! {},
+/// List item for a link, to be placed in `<ul class="links">`.
+pub fn view_link(link: &Link) -> Html {
+    html! { <li><a href={link.url}>{link.kind.label()}</a></li> }
+}
+
+/// Returns the value of the query parameter `name` in the given location.
+pub fn query_param(location: &Location, name: &str) -> Option<String> {
+    location.query::<HashMap<String, String>>().ok()?.remove(name)
+}
+
+/// Whether the event is directed at a text field or a select box, in which
+/// case single-character shortcuts and pasting must not be intercepted.
+pub fn is_typing(event: &Event) -> bool {
+    match event.target().and_then(|t| t.dyn_into::<HtmlElement>().ok()) {
+        Some(elem) => {
+            matches!(elem.tag_name().as_str(), "INPUT" | "TEXTAREA" | "SELECT")
+                || elem.is_content_editable()
+        }
+        None => false,
     }
 }
+
+/// Whether the event is directed at the search box in the header.
+pub fn is_search_box(event: &Event) -> bool {
+    event
+        .target()
+        .and_then(|t| t.dyn_into::<Element>().ok())
+        .is_some_and(|elem| elem.id() == "query")
+}
+
+/// Whether any modifier key other than shift is held during the keyboard
+/// event, which means it's meant for the browser rather than for us.
+pub fn has_modifier(event: &KeyboardEvent) -> bool {
+    event.ctrl_key() || event.alt_key() || event.meta_key()
+}
@@ -0,0 +1,94 @@
+//! Rust version numbers.
+
+use std::{fmt, str::FromStr};
+
+/// A Rust version number, like `1.52` or `1.52.1`.
+///
+/// Versions are ordered by their components, with a missing patch component
+/// ordered before any explicit one (`1.52` < `1.52.0` < `1.52.1`).
+#[derive(Copy, Clone, Debug, Default, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct RustVersion {
+    pub major: u16,
+    pub minor: u16,
+    pub patch: Option<u16>,
+}
+
+impl RustVersion {
+    pub const fn new(major: u16, minor: u16, patch: Option<u16>) -> Self {
+        Self { major, minor, patch }
+    }
+
+    /// This version with the patch component removed, e.g. `1.52` for
+    /// `1.52.1`.
+    pub const fn without_patch(self) -> Self {
+        Self { patch: None, ..self }
+    }
+}
+
+impl fmt::Display for RustVersion {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}.{}", self.major, self.minor)?;
+        if let Some(patch) = self.patch {
+            write!(f, ".{patch}")?;
+        }
+        Ok(())
+    }
+}
+
+impl FromStr for RustVersion {
+    type Err = ParseVersionError;
+
+    /// Parses a version number of the form `x.y` or `x.y.z`.
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let mut components = s.split('.').map(|c| {
+            // u16::from_str accepts a leading '+'
+            c.bytes().all(|b| b.is_ascii_digit()).then(|| c.parse().ok()).flatten()
+        });
+
+        match (components.next(), components.next(), components.next(), components.next()) {
+            (Some(Some(major)), Some(Some(minor)), None, None) => Ok(Self::new(major, minor, None)),
+            (Some(Some(major)), Some(Some(minor)), Some(Some(patch)), None) => {
+                Ok(Self::new(major, minor, Some(patch)))
+            }
+            _ => Err(ParseVersionError(s.to_owned())),
+        }
+    }
+}
+
+#[derive(Debug)]
+pub struct ParseVersionError(String);
+
+impl fmt::Display for ParseVersionError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "invalid version number `{}`, expected x.y or x.y.z", self.0)
+    }
+}
+
+impl std::error::Error for ParseVersionError {}
+
+#[cfg(test)]
+mod tests {
+    use super::RustVersion;
+
+    #[test]
+    fn parse_and_display() {
+        for s in ["1.0", "1.52", "1.52.1", "2.0.0"] {
+            assert_eq!(s.parse::<RustVersion>().unwrap().to_string(), s);
+        }
+        for s in ["", "1", "1.", "1.x", "1.52.1.0", "1.+52", "v1.52"] {
+            assert!(s.parse::<RustVersion>().is_err(), "{s}");
+        }
+    }
+
+    #[test]
+    fn ordering() {
+        let mut versions: Vec<RustVersion> = ["1.9", "2.0", "1.52.1", "1.10", "1.52", "1.52.0"]
+            .iter()
+            .map(|s| s.parse().unwrap())
+            .collect();
+        versions.sort();
+
+        let sorted: Vec<_> = versions.iter().map(ToString::to_string).collect();
+        assert_eq!(sorted, ["1.9", "1.10", "1.52", "1.52.0", "1.52.1", "2.0"]);
+    }
+}
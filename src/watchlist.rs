@@ -0,0 +1,182 @@
+//! Features the user watches, e.g. unstable ones waiting for stabilization.
+//!
+//! The watchlist is kept in localStorage along with the status each feature
+//! had on the last visit, so features that got stabilized or moved to another
+//! channel since then can be highlighted.
+
+use std::collections::HashMap;
+
+use gloo_utils::window;
+
+use crate::{data::Channel, FeatureData, FEATURES};
+
+const STORAGE_KEY: &str = "watchlist";
+
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct Watchlist {
+    /// Watched features, in the order they were added
+    slugs: Vec<&'static str>,
+    /// Previous status of the watched features that changed since the last
+    /// visit
+    changes: HashMap<&'static str, String>,
+}
+
+impl Watchlist {
+    /// Loads the watchlist and records the current status of its features, so
+    /// changes are only highlighted on the first visit after they happened.
+    pub fn load() -> Self {
+        let storage = window().local_storage().ok().flatten();
+        let stored = storage.and_then(|st| st.get_item(STORAGE_KEY).ok().flatten());
+        let this = Self::from_stored(&stored.unwrap_or_default(), FEATURES);
+        this.store();
+        this
+    }
+
+    pub fn store(&self) {
+        if let Ok(Some(st)) = window().local_storage() {
+            if self.slugs.is_empty() {
+                st.remove_item(STORAGE_KEY).unwrap();
+            } else {
+                st.set_item(STORAGE_KEY, &self.to_stored(FEATURES)).unwrap();
+            }
+        }
+    }
+
+    /// Parses the stored watchlist, one `{slug}\t{status}` line per feature.
+    /// Features that no longer exist are dropped.
+    fn from_stored(stored: &str, features: &'static [FeatureData]) -> Self {
+        let mut this = Self::default();
+        for line in stored.lines() {
+            let (slug, previous_status) = line.split_once('\t').unwrap_or((line, ""));
+            let Some(f) = features.iter().find(|f| f.slug == slug) else { continue };
+            if this.contains(f.slug) {
+                continue;
+            }
+
+            this.slugs.push(f.slug);
+            if !previous_status.is_empty() && previous_status != status(f) {
+                this.changes.insert(f.slug, previous_status.to_owned());
+            }
+        }
+        this
+    }
+
+    fn to_stored(&self, features: &[FeatureData]) -> String {
+        let lines = self.slugs.iter().filter_map(|&slug| {
+            let f = features.iter().find(|f| f.slug == slug)?;
+            Some(format!("{slug}\t{}\n", status(f)))
+        });
+        lines.collect()
+    }
+
+    pub fn contains(&self, slug: &str) -> bool {
+        self.slugs.contains(&slug)
+    }
+
+    /// Adds the feature to the watchlist, or removes it if it is already
+    /// watched.
+    pub fn toggle(&mut self, slug: &'static str) {
+        if self.contains(slug) {
+            self.slugs.retain(|&s| s != slug);
+            self.changes.remove(slug);
+        } else {
+            self.slugs.push(slug);
+        }
+    }
+
+    /// The watched features, in the order they were added.
+    pub fn features(&self) -> impl Iterator<Item = &'static FeatureData> + '_ {
+        self.slugs.iter().filter_map(|&slug| FEATURES.iter().find(|f| f.slug == slug))
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.slugs.is_empty()
+    }
+
+    /// The status the feature had on the last visit, if it changed since.
+    pub fn previous_status(&self, slug: &str) -> Option<&str> {
+        self.changes.get(slug).map(String::as_str)
+    }
+
+    /// Number of watched features that changed since the last visit.
+    pub fn num_changes(&self) -> usize {
+        self.changes.len()
+    }
+}
+
+/// Short description of where the feature is available, e.g. `unstable` or
+/// `1.65 (beta)`.
+pub fn status(f: &FeatureData) -> String {
+    match f.version {
+        None => "unstable".to_owned(),
+        Some(v) => {
+            let channel = match v.channel {
+                Channel::Nightly => "nightly",
+                Channel::Beta => "beta",
+                Channel::Stable => "stable",
+            };
+            format!("{} ({channel})", v.number)
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::Watchlist;
+    use crate::{
+        data::{Channel, FeatureData, VersionData},
+        version::RustVersion,
+    };
+
+    static BETA: VersionData = VersionData {
+        number: RustVersion::new(1, 88, None),
+        channel: Channel::Beta,
+        release_date: None,
+        release_date_estimated: false,
+        links: &[],
+        patch_releases: &[],
+    };
+
+    const fn feature(slug: &'static str, version: Option<&'static VersionData>) -> FeatureData {
+        FeatureData {
+            title: slug,
+            description: None,
+            flag: None,
+            slug,
+            version,
+            links: &[],
+            items: &[],
+            aliases: &[],
+        }
+    }
+
+    static FEATURES: &[FeatureData] =
+        &[feature("let_chains", Some(&BETA)), feature("never_type", None)];
+
+    #[test]
+    fn changes_since_last_visit() {
+        let stored = "never_type\tunstable\nlet_chains\tunstable\nremoved_feature\tunstable\n";
+        let watchlist = Watchlist::from_stored(stored, FEATURES);
+
+        assert_eq!(watchlist.slugs, ["never_type", "let_chains"]);
+        assert_eq!(watchlist.num_changes(), 1);
+        assert_eq!(watchlist.previous_status("let_chains"), Some("unstable"));
+        assert_eq!(watchlist.previous_status("never_type"), None);
+
+        assert_eq!(
+            watchlist.to_stored(FEATURES),
+            "never_type\tunstable\nlet_chains\t1.88 (beta)\n"
+        );
+    }
+
+    #[test]
+    fn toggle() {
+        let mut watchlist = Watchlist::from_stored("let_chains\tunstable\n", FEATURES);
+        watchlist.toggle("never_type");
+        watchlist.toggle("let_chains");
+
+        assert!(watchlist.contains("never_type"));
+        assert!(!watchlist.contains("let_chains"));
+        assert_eq!(watchlist.num_changes(), 0);
+    }
+}
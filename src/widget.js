@@ -0,0 +1,29 @@
+import init, { mountBadge, mountCard } from '../pkg/widget/caniuse_widget.js';
+
+// Everything is loaded from wherever this script is served from
+var base = document.currentScript.src;
+var ready = init(new URL('caniuse_widget.wasm', base));
+
+function defineElement(name, mount) {
+    customElements.define(name, class extends HTMLElement {
+        async connectedCallback() {
+            if (this.shadowRoot) {
+                return;
+            }
+
+            // The shadow root keeps the host page's styles out and ours in
+            var root = this.attachShadow({ mode: 'open' });
+            var style = document.createElement('link');
+            style.rel = 'stylesheet';
+            style.href = new URL('widget.css', base);
+            var container = document.createElement('span');
+            root.append(style, container);
+
+            await ready;
+            mount(container, this.getAttribute('slug') || '');
+        }
+    });
+}
+
+defineElement('caniuse-badge', mountBadge);
+defineElement('caniuse-card', mountCard);
@@ -0,0 +1,77 @@
+//! Entry point for embedding features in other sites, without the router,
+//! header and the rest of the app. `src/widget.js` wraps this in the
+//! `<caniuse-badge>` and `<caniuse-card>` custom elements.
+
+use wasm_bindgen::{prelude::wasm_bindgen, JsError};
+use web_sys::Element;
+use yew::{html, props, Component, Context, Html, Properties};
+
+use crate::{components::FeatureEntry, data::Channel, markup, util::Void, FeatureData, FEATURES};
+
+/// Renders a badge like "since Rust 1.65" for the feature into `element`.
+#[wasm_bindgen(js_name = mountBadge)]
+pub fn mount_badge(element: Element, slug: &str) -> Result<(), JsError> {
+    let data = find_feature(slug)?;
+    yew::Renderer::<Badge>::with_root_and_props(element, BadgeProps { data }).render();
+    Ok(())
+}
+
+/// Renders the feature the way it is shown in lists on caniuse.rs into
+/// `element`.
+#[wasm_bindgen(js_name = mountCard)]
+pub fn mount_card(element: Element, slug: &str) -> Result<(), JsError> {
+    let data = find_feature(slug)?;
+    let props = props!(FeatureEntry::Properties { data, external: true });
+    yew::Renderer::<FeatureEntry>::with_root_and_props(element, props).render();
+    Ok(())
+}
+
+fn find_feature(slug: &str) -> Result<FeatureData, JsError> {
+    match FEATURES.iter().find(|f| f.slug == slug) {
+        Some(&f) => Ok(f),
+        None => Err(JsError::new(&format!("caniuse.rs: unknown feature `{slug}`"))),
+    }
+}
+
+#[derive(Clone, PartialEq, Properties)]
+struct BadgeProps {
+    data: FeatureData,
+}
+
+struct Badge;
+
+impl Component for Badge {
+    type Message = Void;
+    type Properties = BadgeProps;
+
+    fn create(_: &Context<Self>) -> Self {
+        Self
+    }
+
+    fn update(&mut self, _: &Context<Self>, void: Self::Message) -> bool {
+        match void {}
+    }
+
+    fn changed(&mut self, _: &Context<Self>, _old_props: &Self::Properties) -> bool {
+        true
+    }
+
+    fn view(&self, ctx: &Context<Self>) -> Html {
+        let f = &ctx.props().data;
+        let (class, text) = match f.version {
+            None => ("none", "unstable".to_owned()),
+            Some(v) => match v.channel {
+                Channel::Stable => ("stable", format!("since Rust {}", v.number)),
+                Channel::Beta => ("beta", format!("Rust {} (beta)", v.number)),
+                Channel::Nightly => ("nightly", format!("Rust {} (nightly)", v.number)),
+            },
+        };
+
+        html! {
+            <a class={format!("badge {class}")} href={format!("https://caniuse.rs/features/{}", f.slug)}
+                title={markup::to_plain(f.title)}>
+                {text}
+            </a>
+        }
+    }
+}
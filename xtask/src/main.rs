@@ -1,11 +1,16 @@
 use std::{
+    convert::Infallible,
     fs, io,
     net::{Ipv6Addr, SocketAddr},
+    path::Path,
+    task::{Context, Poll},
 };
 
 use clap::{Parser, Subcommand};
+use http::Request;
 use hyper_util::service::TowerToHyperService;
 use tower_http::services::{ServeDir, ServeFile};
+use tower_service::Service;
 use xshell::{cmd, Shell};
 
 #[derive(Parser)]
@@ -19,30 +24,60 @@ enum Command {
     Build {
         #[clap(long)]
         dev: bool,
+        /// Also build the embeddable badges and cards
+        #[clap(long)]
+        widget: bool,
+        /// Date to work out release channels for (yyyy-mm-dd), defaults to
+        /// today
+        #[clap(long)]
+        date: Option<String>,
     },
     Serve {
         #[clap(long)]
         release: bool,
     },
-    Deploy,
+    Deploy {
+        /// Date to work out release channels for (yyyy-mm-dd), defaults to
+        /// today
+        #[clap(long)]
+        date: Option<String>,
+    },
 }
 
 fn main() -> anyhow::Result<()> {
     let args = CliArgs::parse();
     match args.command {
-        Command::Build { dev } => build(dev),
+        Command::Build { dev, widget, date } => build(dev, widget, date),
         Command::Serve { release } => serve(release),
-        Command::Deploy => deploy(),
+        Command::Deploy { date } => deploy(date),
     }
 }
 
-fn build(dev: bool) -> anyhow::Result<()> {
+fn build(dev: bool, widget: bool, date: Option<String>) -> anyhow::Result<()> {
     let sh = Shell::new()?;
 
+    // The build script only reruns when the data or this variable changes, so
+    // it has to be set for channels to be up to date
+    let date = match date {
+        Some(date) => date,
+        None => cmd!(sh, "date -u +%F").read()?,
+    };
+    let _date = sh.push_env("CANIUSE_BUILD_DATE", date);
+
     cmd!(sh, "wasm-pack build --no-typescript --target web").args(dev.then_some("--dev")).run()?;
     fs::copy("pkg/caniuse_rs_bg.wasm", "public/caniuse_rs.wasm")?;
     cmd!(sh, "rollup src/main.js --format iife --file public/caniuse_rs.js").run()?;
 
+    if widget {
+        cmd!(sh, "wasm-pack build --no-typescript --target web --out-dir pkg/widget")
+            .args(["--out-name", "caniuse_widget"])
+            .args(dev.then_some("--dev"))
+            .args(["--", "--no-default-features", "--features", "widget"])
+            .run()?;
+        fs::copy("pkg/widget/caniuse_widget_bg.wasm", "public/caniuse_widget.wasm")?;
+        cmd!(sh, "rollup src/widget.js --format iife --file public/caniuse_widget.js").run()?;
+    }
+
     let static_files: Vec<_> =
         fs::read_dir("static")?.map(|entry| Ok(entry?.path())).collect::<io::Result<_>>()?;
     cmd!(sh, "cp -r {static_files...} public/").run()?;
@@ -52,7 +87,7 @@ fn build(dev: bool) -> anyhow::Result<()> {
 
 #[tokio::main]
 async fn serve(release: bool) -> anyhow::Result<()> {
-    build(!release)?;
+    build(!release, false, None)?;
 
     println!("Starting development server on http://localhost:8000");
 
@@ -64,9 +99,9 @@ async fn serve(release: bool) -> anyhow::Result<()> {
 
         tokio::spawn(async move {
             let socket = hyper_util::rt::TokioIo::new(socket);
-            let service = TowerToHyperService::new(
+            let service = TowerToHyperService::new(StaticPages(
                 ServeDir::new("public").fallback(ServeFile::new("public/index.html")),
-            );
+            ));
 
             if let Err(err) =
                 hyper_util::server::conn::auto::Builder::new(hyper_util::rt::TokioExecutor::new())
@@ -79,8 +114,32 @@ async fn serve(release: bool) -> anyhow::Result<()> {
     }
 }
 
-fn deploy() -> anyhow::Result<()> {
-    build(false)?;
+/// Serves the page the build script rendered for a path, like
+/// `features/let_else.html` for `/features/let_else`, if there is one. Web
+/// servers the site is deployed to need the same rule, see README.md.
+#[derive(Clone)]
+struct StaticPages(ServeDir<ServeFile>);
+
+impl<B: Send + 'static> Service<Request<B>> for StaticPages {
+    type Response = <ServeDir<ServeFile> as Service<Request<B>>>::Response;
+    type Error = Infallible;
+    type Future = <ServeDir<ServeFile> as Service<Request<B>>>::Future;
+
+    fn poll_ready(&mut self, cx: &mut Context<'_>) -> Poll<Result<(), Self::Error>> {
+        Service::<Request<B>>::poll_ready(&mut self.0, cx)
+    }
+
+    fn call(&mut self, mut req: Request<B>) -> Self::Future {
+        let page = format!("{}.html", req.uri().path());
+        if Path::new("public").join(page.trim_start_matches('/')).is_file() {
+            *req.uri_mut() = page.parse().unwrap();
+        }
+        self.0.call(req)
+    }
+}
+
+fn deploy(date: Option<String>) -> anyhow::Result<()> {
+    build(false, true, date)?;
 
     let sh = Shell::new()?;
     cmd!(sh, "rsync -rzz public caniuse.rs:/tmp/caniuse/").run()?;
@@ -92,5 +151,18 @@ fn deploy() -> anyhow::Result<()> {
     "#;
     cmd!(sh, "ssh caniuse.rs {ssh_cmds}").run()?;
 
+    // The web server config is not part of this repository, see the
+    // embedding section of README.md
+    let headers = cmd!(sh, "curl -sI https://caniuse.rs/caniuse_widget.wasm").read()?;
+    let allows_cors = headers
+        .lines()
+        .any(|line| line.to_ascii_lowercase().starts_with("access-control-allow-origin:"));
+    if !allows_cors {
+        eprintln!(
+            "warning: caniuse_widget.wasm is served without Access-Control-Allow-Origin, \
+             other sites can't load the widget"
+        );
+    }
+
     Ok(())
 }